@@ -0,0 +1,138 @@
+// Compares the day 3 bitmask solvers with the HashSet-based solution they
+// replaced, on generated input. Run with `cargo bench --bench day3`.
+use std::{
+    collections::HashSet,
+    time::{Duration, Instant},
+};
+
+use advent2022::puzzles::{params::Params, year2022::day3};
+use itertools::Itertools;
+
+const RUNS: usize = 10;
+
+fn main() {
+    let input = generate_input(20_000, 200);
+    println!("{} rucksacks, best of {} runs", input.lines().count(), RUNS);
+    compare(
+        "part 1",
+        || day3::part1(input.clone()),
+        || hash_set_part1(&input),
+    );
+    compare(
+        "part 2",
+        || day3::part2(input.clone(), &Params::new()),
+        || hash_set_part2(&input),
+    );
+}
+
+fn compare(name: &str, bitmask: impl Fn() -> u32, hash_set: impl Fn() -> u32) {
+    let (bitmask_answer, bitmask_time) = best_of(bitmask);
+    let (hash_set_answer, hash_set_time) = best_of(hash_set);
+    assert_eq!(
+        bitmask_answer, hash_set_answer,
+        "{}: the solutions disagree",
+        name
+    );
+    println!(
+        "{}  bitmask {:>10.1?}  hash set {:>10.1?}  ({:.1}x faster)",
+        name,
+        bitmask_time,
+        hash_set_time,
+        hash_set_time.as_secs_f64() / bitmask_time.as_secs_f64()
+    );
+}
+
+fn best_of(solve: impl Fn() -> u32) -> (u32, Duration) {
+    (0..RUNS)
+        .map(|_| {
+            let start = Instant::now();
+            let answer = solve();
+            (answer, start.elapsed())
+        })
+        .min_by_key(|(_, time)| *time)
+        .unwrap()
+}
+
+fn hash_set_part1(input: &str) -> u32 {
+    input
+        .lines()
+        .map(|rucksack| {
+            let (first, second) = rucksack.split_at(rucksack.len() / 2);
+            let seen: HashSet<char> = first.chars().collect();
+            second
+                .chars()
+                .find(|item| seen.contains(item))
+                .map_or(0, priority)
+        })
+        .sum()
+}
+
+fn hash_set_part2(input: &str) -> u32 {
+    input
+        .lines()
+        .chunks(3)
+        .into_iter()
+        .map(|group| {
+            let common = group
+                .map(|rucksack| rucksack.chars().collect::<HashSet<char>>())
+                .reduce(|common, items| &common & &items)
+                .unwrap_or_default();
+            priority(common.into_iter().exactly_one().unwrap())
+        })
+        .sum()
+}
+
+fn priority(item: char) -> u32 {
+    match item {
+        'a'..='z' => item as u32 - 'a' as u32 + 1,
+        _ => item as u32 - 'A' as u32 + 27,
+    }
+}
+
+fn item(priority: u32) -> char {
+    match priority {
+        1..=26 => (b'a' + priority as u8 - 1) as char,
+        _ => (b'A' + priority as u8 - 27) as char,
+    }
+}
+
+// Generates valid puzzle input with `groups` groups of three rucksacks, each
+// with `compartment` (at least 2) items per compartment, from a fixed seed.
+fn generate_input(groups: usize, compartment: usize) -> String {
+    let mut seed: u64 = 0x2022_0003;
+    let mut random = move |n: usize| {
+        // xorshift64
+        seed ^= seed << 13;
+        seed ^= seed >> 7;
+        seed ^= seed << 17;
+        (seed % n as u64) as usize
+    };
+    let mut input = String::new();
+    for _ in 0..groups {
+        // Give each member of the group 17 item types of its own, so that the
+        // badge is the only item type they all have. Each rucksack's
+        // compartments share one of its member's item types.
+        let mut priorities: Vec<u32> = (1..=52).collect();
+        let badge = priorities.swap_remove(random(52));
+        for i in (1..priorities.len()).rev() {
+            priorities.swap(i, random(i + 1));
+        }
+        for own in priorities.chunks(17) {
+            let (shared, first, second) = (own[0], &own[1..9], &own[9..]);
+            let mut compartments = [first, second].map(|pool| {
+                (0..compartment)
+                    .map(|_| item(pool[random(pool.len())]))
+                    .collect::<Vec<char>>()
+            });
+            let badge_at = random(compartment);
+            let shared_at = (badge_at + 1 + random(compartment - 1)) % compartment;
+            compartments[0][badge_at] = item(badge);
+            compartments[0][shared_at] = item(shared);
+            compartments[1][random(compartment)] = item(shared);
+            let rucksack: String = compartments.concat().into_iter().collect();
+            input.push_str(&rucksack);
+            input.push('\n');
+        }
+    }
+    input
+}
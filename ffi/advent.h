@@ -0,0 +1,42 @@
+/* Generated from src/ffi.rs by `UPDATE_HEADER=1 cargo test test_header`. Do not edit. */
+
+#ifndef ADVENT_H
+#define ADVENT_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* Result codes from advent_solve. */
+typedef enum {
+    ADVENT_OK = 0,
+    ADVENT_UNKNOWN_YEAR = 1,
+    ADVENT_UNKNOWN_DAY = 2,
+    ADVENT_UNKNOWN_PART = 3,
+    ADVENT_FAILED = 4,
+    ADVENT_INVALID_ARGUMENT = 5,
+} advent_status;
+
+/* The library version. Must not be freed. */
+const char *advent_version(void);
+
+/* Writes up to capacity of the year's day numbers to days, and returns how
+ * many days the year has (0 for unknown years). */
+size_t advent_days(uint32_t year, uint32_t *days, size_t capacity);
+
+/* Solves a puzzle part. Sets *answer to the answer, or to an error message if
+ * the result isn't ADVENT_OK. Free *answer with advent_free_string. */
+int advent_solve(uint32_t year, uint32_t day, uint32_t part, const uint8_t *input,
+                 size_t input_len, char **answer);
+
+/* Frees a string returned by advent_solve. */
+void advent_free_string(char *s);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* ADVENT_H */
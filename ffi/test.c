@@ -0,0 +1,57 @@
+/* Exercises the C ABI. From the repository root:
+ *
+ *     cargo build
+ *     cc -Wall -Wextra -Iffi ffi/test.c -Ltarget/debug -ladvent2022 -o target/ffi-test
+ *     LD_LIBRARY_PATH=target/debug target/ffi-test
+ */
+
+#include <stdio.h>
+#include <string.h>
+
+#include "advent.h"
+
+static int failures = 0;
+
+static void expect(uint32_t day, uint32_t part, const char *input, int status,
+                   const char *expected) {
+    char *answer = NULL;
+    int actual = advent_solve(2022, day, part, (const uint8_t *)input, strlen(input), &answer);
+    if (actual != status || answer == NULL || strcmp(answer, expected) != 0) {
+        fprintf(stderr, "day %u part %u: expected %d \"%s\", got %d \"%s\"\n", day, part,
+                status, expected, actual, answer ? answer : "(null)");
+        failures++;
+    }
+    advent_free_string(answer);
+}
+
+int main(void) {
+    printf("advent %s\n", advent_version());
+
+    uint32_t days[32];
+    size_t count = advent_days(2022, days, sizeof days / sizeof days[0]);
+    if (count == 0 || days[0] != 1) {
+        fprintf(stderr, "expected 2022 to start at day 1, got %zu days\n", count);
+        failures++;
+    }
+    if (advent_days(1999, NULL, 0) != 0) {
+        fprintf(stderr, "expected no days in 1999\n");
+        failures++;
+    }
+
+    expect(2, 1, "A Y\nB X\nC Z\n", ADVENT_OK, "15");
+    expect(6, 2, "mjqjpqmgbljsphdztnvjfqwrcgsmlb", ADVENT_OK, "19");
+    expect(2, 3, "", ADVENT_UNKNOWN_PART, "Unknown puzzle part: day 2, part 3");
+    expect(99, 1, "", ADVENT_UNKNOWN_DAY, "Unknown puzzle day: 99");
+
+    char *answer = NULL;
+    if (advent_solve(2022, 2, 1, (const uint8_t *)"Q Y\n", 4, &answer) != ADVENT_FAILED) {
+        fprintf(stderr, "expected invalid input to fail: %s\n", answer);
+        failures++;
+    }
+    advent_free_string(answer);
+
+    if (failures == 0) {
+        printf("%zu days, all checks passed\n", count);
+    }
+    return failures == 0 ? 0 : 1;
+}
@@ -0,0 +1,312 @@
+use std::{
+    cell::Cell,
+    time::{Duration, Instant},
+};
+
+use crate::puzzles::{self, params::Params, SolveError};
+
+// Counts allocations made on the current thread while `count` is running.
+// Counting is per thread so that concurrent solvers (in the server, or in
+// tests) don't see each other's allocations. Outside of `count`, every
+// allocation only pays for checking a thread-local flag. Builds without the
+// alloc-stats feature don't install the allocator at all, and count nothing.
+#[cfg(feature = "alloc-stats")]
+mod counting {
+    use std::alloc::{GlobalAlloc, Layout, System};
+
+    use super::COUNTER;
+
+    struct CountingAllocator;
+
+    #[global_allocator]
+    static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+    // Records an allocation of `allocated` bytes (None for frees), which changed
+    // the number of live bytes by `live_change`.
+    fn record(allocated: Option<usize>, live_change: i64) {
+        // Threads that are shutting down can't be counted, but can still allocate.
+        let _ = COUNTER.try_with(|counter| {
+            let mut state = counter.get();
+            if !state.counting {
+                return;
+            }
+            if let Some(bytes) = allocated {
+                state.allocations.count += 1;
+                state.allocations.bytes += bytes as u64;
+            }
+            state.live_bytes += live_change;
+            state.allocations.peak_bytes = state
+                .allocations
+                .peak_bytes
+                .max(state.live_bytes.max(0) as u64);
+            counter.set(state);
+        });
+    }
+
+    unsafe impl GlobalAlloc for CountingAllocator {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            let ptr = System.alloc(layout);
+            if !ptr.is_null() {
+                record(Some(layout.size()), layout.size() as i64);
+            }
+            ptr
+        }
+
+        unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+            let ptr = System.alloc_zeroed(layout);
+            if !ptr.is_null() {
+                record(Some(layout.size()), layout.size() as i64);
+            }
+            ptr
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            System.dealloc(ptr, layout);
+            record(None, -(layout.size() as i64));
+        }
+
+        // Counts as one allocation of the new size.
+        unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+            let new_ptr = System.realloc(ptr, layout, new_size);
+            if !new_ptr.is_null() {
+                record(Some(new_size), new_size as i64 - layout.size() as i64);
+            }
+            new_ptr
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Allocations {
+    pub count: u64,
+    pub bytes: u64,
+    // The most bytes allocated and not yet freed at any one time, counting
+    // only allocations made while counting.
+    pub peak_bytes: u64,
+}
+
+// Without the counting allocator, only `count` uses the counter, and nothing
+// reads what it's counting.
+#[derive(Clone, Copy)]
+#[cfg_attr(not(feature = "alloc-stats"), allow(dead_code))]
+struct Counter {
+    counting: bool,
+    live_bytes: i64,
+    allocations: Allocations,
+}
+
+impl Counter {
+    const IDLE: Counter = Counter {
+        counting: false,
+        live_bytes: 0,
+        allocations: Allocations {
+            count: 0,
+            bytes: 0,
+            peak_bytes: 0,
+        },
+    };
+}
+
+thread_local! {
+    // Initialized without allocating, so the allocator can use it.
+    static COUNTER: Cell<Counter> = const { Cell::new(Counter::IDLE) };
+}
+
+// Whether this build counts allocations. Says how to get one that does if
+// not.
+pub fn available() -> bool {
+    if !cfg!(feature = "alloc-stats") {
+        eprintln!("Allocation stats are not available: built without the alloc-stats feature");
+    }
+    cfg!(feature = "alloc-stats")
+}
+
+// Restores the counter when counting finishes, even if the counted function
+// unwinds.
+struct CountingGuard(Counter);
+
+impl Drop for CountingGuard {
+    fn drop(&mut self) {
+        COUNTER.with(|counter| counter.set(self.0));
+    }
+}
+
+// Runs a function, counting the allocations it makes on this thread.
+pub fn count<F, T>(f: F) -> (T, Allocations)
+where
+    F: FnOnce() -> T,
+{
+    let guard = CountingGuard(COUNTER.with(|counter| {
+        counter.replace(Counter {
+            counting: true,
+            ..Counter::IDLE
+        })
+    }));
+    let result = f();
+    let allocations = COUNTER.with(|counter| counter.get().allocations);
+    drop(guard);
+    (result, allocations)
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Phase {
+    pub time: Duration,
+    pub allocations: Allocations,
+}
+
+// How a solver run splits between parsing the input and solving the puzzle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Profile {
+    // None for days that parse their input as they solve it.
+    pub parse: Option<Phase>,
+    pub solve: Phase,
+}
+
+// Runs a day's parser on its own and then the whole solver, and attributes
+// whatever the solver did beyond parsing to solving. The solve phase's peak
+// includes the parsed input, since it's live while the puzzle is solved.
+pub fn profile(
+    year: u32,
+    day: u32,
+    part: u32,
+    input: &str,
+    params: &Params,
+) -> Result<(String, Profile), SolveError> {
+    let parse = match puzzles::find_day(year, day).and_then(|day| day.parse) {
+        Some(parse) => {
+            let ((parsed, time), allocations) =
+                count(|| timed(|| puzzles::catch_failure(|| parse(input))));
+            parsed.map_err(SolveError::Failed)?;
+            Some(Phase { time, allocations })
+        }
+        None => None,
+    };
+    // Copy the input before counting, so that the copy isn't charged to solving.
+    let input = input.to_string();
+    let ((answer, time), allocations) =
+        count(|| timed(|| puzzles::solve(year, day, part, input, params)));
+    let answer = answer?;
+
+    let parsed = parse.unwrap_or_default();
+    let solve = Phase {
+        time: time.saturating_sub(parsed.time),
+        allocations: Allocations {
+            count: allocations.count.saturating_sub(parsed.allocations.count),
+            bytes: allocations.bytes.saturating_sub(parsed.allocations.bytes),
+            peak_bytes: allocations.peak_bytes,
+        },
+    };
+    Ok((answer, Profile { parse, solve }))
+}
+
+fn timed<F, T>(f: F) -> (T, Duration)
+where
+    F: FnOnce() -> T,
+{
+    let start = Instant::now();
+    let result = f();
+    (result, start.elapsed())
+}
+
+pub fn format_bytes(bytes: u64) -> String {
+    match bytes {
+        0..=1023 => format!("{} B", bytes),
+        1024..=1_048_575 => format!("{:.1} KiB", bytes as f64 / 1024.0),
+        _ => format!("{:.1} MiB", bytes as f64 / 1_048_576.0),
+    }
+}
+
+impl Phase {
+    pub fn to_text(self) -> String {
+        format!(
+            "{:>10.1?}  {:>8} allocations  {:>10} allocated  {:>10} peak",
+            self.time,
+            self.allocations.count,
+            format_bytes(self.allocations.bytes),
+            format_bytes(self.allocations.peak_bytes)
+        )
+    }
+
+    pub fn to_json(self) -> String {
+        format!(
+            "{{\"time_us\":{},\"allocations\":{},\"bytes\":{},\"peak_bytes\":{}}}",
+            self.time.as_micros(),
+            self.allocations.count,
+            self.allocations.bytes,
+            self.allocations.peak_bytes
+        )
+    }
+}
+
+impl Profile {
+    pub fn to_text(self) -> String {
+        let parse = match self.parse {
+            Some(parse) => parse.to_text(),
+            None => "(parsed while solving)".into(),
+        };
+        format!("parse  {}\nsolve  {}", parse, self.solve.to_text())
+    }
+
+    pub fn to_json(self) -> String {
+        format!(
+            "{{\"parse\":{},\"solve\":{}}}",
+            self.parse.map_or("null".into(), |parse| parse.to_json()),
+            self.solve.to_json()
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::panic;
+
+    use super::*;
+
+    #[test]
+    #[cfg(feature = "alloc-stats")]
+    fn test_count() {
+        let (sum, allocations) = count(|| {
+            let mut numbers = Vec::with_capacity(1000);
+            numbers.extend(0..1000u64);
+            let copy = numbers.clone();
+            drop(numbers);
+            copy.iter().sum::<u64>()
+        });
+        assert_eq!(sum, 499500);
+        assert_eq!(allocations.count, 2);
+        assert_eq!(allocations.bytes, 16000);
+        assert_eq!(allocations.peak_bytes, 16000);
+
+        // Nothing is counted once counting is over.
+        let (_, allocations) = count(|| ());
+        assert_eq!(allocations, Allocations::default());
+    }
+
+    #[test]
+    fn test_count_unwinding() {
+        let failed = puzzles::catch_failure(|| count(|| panic::resume_unwind(Box::new(()))));
+        assert!(failed.is_err());
+        assert!(!COUNTER.with(|counter| counter.get().counting));
+    }
+
+    #[test]
+    #[cfg(feature = "alloc-stats")]
+    fn test_profile() {
+        let (answer, rope) = profile(2022, 9, 1, "R 4\nU 4\n", &Params::new()).unwrap();
+        assert_eq!(answer, "7");
+        assert!(rope.parse.unwrap().allocations.count > 0);
+        assert!(rope.solve.allocations.count > 0);
+        assert!(rope.solve.allocations.peak_bytes > 0);
+
+        // Day 6 has no separate parser.
+        let (_, marker) = profile(2022, 6, 1, "abcd", &Params::new()).unwrap();
+        assert_eq!(marker.parse, None);
+    }
+
+    #[test]
+    fn test_format_bytes() {
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(3 << 20), "3.0 MiB");
+    }
+}
@@ -0,0 +1,369 @@
+use std::{
+    fs,
+    process::exit,
+    time::{Duration, Instant},
+};
+
+use clap::Args;
+
+use crate::{
+    allocations::{self, Profile},
+    config::Config,
+    inputs,
+    output::Format,
+    puzzles::{
+        self,
+        params::{self, Params},
+    },
+};
+
+#[derive(Args, Debug)]
+pub struct BenchArgs {
+    /// Year of puzzles to benchmark
+    #[arg(short, long, default_value_t = puzzles::DEFAULT_YEAR)]
+    year: u32,
+
+    /// Day number of puzzle to benchmark [default: every day with an input file]
+    #[arg(short, long)]
+    day: Option<u32>,
+
+    /// Part number of puzzle to benchmark [default: both parts]
+    #[arg(short, long)]
+    part: Option<u32>,
+
+    /// Path to puzzle input file [default: inputs/<YEAR>/day<DAY>.txt]
+    #[arg(short, long, requires = "day")]
+    input_filepath: Option<String>,
+
+    /// Number of timed runs of each part [default: 10]
+    #[arg(long)]
+    runs: Option<usize>,
+
+    /// Number of untimed runs of each part before the timed runs [default: 1]
+    #[arg(long)]
+    warmup: Option<usize>,
+
+    /// Stop timing a part after this many seconds, once it has run at least once
+    #[arg(long, value_name = "SECONDS")]
+    timeout: Option<u64>,
+
+    /// Print results as text or json [default: text]
+    #[arg(long)]
+    format: Option<Format>,
+
+    /// Also report the memory allocated to parse and to solve, from one more run (needs the alloc-stats feature)
+    #[arg(long)]
+    alloc_stats: bool,
+
+    /// Override a solver parameter, as key=value (may be repeated)
+    #[arg(long = "param", value_parser = params::parse_assignment)]
+    params: Vec<(String, String)>,
+}
+
+const DEFAULT_RUNS: usize = 10;
+const DEFAULT_WARMUP: usize = 1;
+
+pub fn bench(args: BenchArgs, config: &Config) {
+    let settings = Settings::new(&args, config).unwrap_or_else(|err| {
+        println!("{}", err);
+        exit(1)
+    });
+    let alloc_stats = args.alloc_stats && allocations::available();
+
+    let Some(year) = puzzles::find_year(args.year) else {
+        println!("Unknown puzzle year: {}", args.year);
+        exit(1)
+    };
+    let days = match args.day {
+        Some(day) if puzzles::find_day(args.year, day).is_none() => {
+            println!("Unknown puzzle day: {}", day);
+            exit(1)
+        }
+        Some(day) => vec![day],
+        None => year.days.iter().map(|day| day.number).collect(),
+    };
+    let parts = args.part.map_or(vec![1, 2], |part| vec![part]);
+
+    let mut failed = false;
+    for day in days {
+        let input_filepath = match inputs::find_input(
+            args.input_filepath.as_deref(),
+            &config.inputs_dir(),
+            args.year,
+            day,
+        ) {
+            Ok(path) => path,
+            // Benchmarking every day skips the days we have no input for.
+            Err(_) if args.day.is_none() => continue,
+            Err(err) => {
+                println!("{}", err);
+                exit(1)
+            }
+        };
+        let input = fs::read_to_string(input_filepath).unwrap_or_else(|err| {
+            println!("Could not open puzzle input file: {}", err);
+            exit(1)
+        });
+        let params: Params = config
+            .params(args.year, day)
+            .into_iter()
+            .chain(args.params.iter().cloned())
+            .collect();
+
+        for part in &parts {
+            let measured = measure(&settings, || {
+                puzzles::solve(args.year, day, *part, input.clone(), &params)
+                    .map(|_| ())
+                    .map_err(|err| err.to_string())
+            });
+            // Profile separately, since counting allocations slows solvers down.
+            let profiled = measured.and_then(|timings| {
+                if !alloc_stats {
+                    return Ok((timings, None));
+                }
+                allocations::profile(args.year, day, *part, &input, &params)
+                    .map(|(_, profile)| (timings, Some(profile)))
+                    .map_err(|err| err.to_string())
+            });
+            match profiled {
+                Ok((timings, profile)) => println!(
+                    "{}",
+                    report(
+                        settings.format,
+                        (args.year, day, *part),
+                        &timings,
+                        profile.as_ref()
+                    )
+                ),
+                Err(err) => {
+                    println!("Day {} part {} failed: {}", day, part, err);
+                    failed = true;
+                }
+            }
+        }
+    }
+    if failed {
+        exit(1)
+    }
+}
+
+// The flags, or else the config file's settings, or else the defaults.
+#[derive(Debug, PartialEq, Eq)]
+struct Settings {
+    runs: usize,
+    warmup: usize,
+    timeout: Option<Duration>,
+    format: Format,
+}
+
+impl Settings {
+    fn new(args: &BenchArgs, config: &Config) -> Result<Settings, String> {
+        Ok(Settings {
+            runs: config.bench_runs(args.runs)?.unwrap_or(DEFAULT_RUNS),
+            warmup: config.bench_warmup(args.warmup)?.unwrap_or(DEFAULT_WARMUP),
+            timeout: config.bench_timeout(args.timeout)?.map(Duration::from_secs),
+            format: config.format(args.format)?.unwrap_or_default(),
+        })
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct Timings {
+    runs: usize,
+    min: Duration,
+    median: Duration,
+    mean: Duration,
+}
+
+// Runs a solver untimed for the warmup runs, then times each of the timed
+// runs. Always makes at least one timed run, and makes no more once the timed
+// runs have taken longer than the timeout.
+fn measure<F>(settings: &Settings, mut solve: F) -> Result<Timings, String>
+where
+    F: FnMut() -> Result<(), String>,
+{
+    for _ in 0..settings.warmup {
+        solve()?;
+    }
+    let mut times: Vec<Duration> = Vec::new();
+    for _ in 0..settings.runs.max(1) {
+        if settings
+            .timeout
+            .is_some_and(|timeout| times.iter().sum::<Duration>() > timeout)
+        {
+            break;
+        }
+        let start = Instant::now();
+        solve()?;
+        times.push(start.elapsed());
+    }
+    times.sort();
+    Ok(Timings {
+        runs: times.len(),
+        min: times[0],
+        median: times[times.len() / 2],
+        mean: times.iter().sum::<Duration>() / times.len() as u32,
+    })
+}
+
+// The timings, followed by the allocations of each phase if they were
+// profiled.
+fn report(
+    format: Format,
+    (year, day, part): (u32, u32, u32),
+    timings: &Timings,
+    profile: Option<&Profile>,
+) -> String {
+    match format {
+        Format::Text => {
+            let mut report = format!(
+                "{} day {:>2} part {}  min {:>10.1?}  median {:>10.1?}  mean {:>10.1?}  ({} runs)",
+                year, day, part, timings.min, timings.median, timings.mean, timings.runs
+            );
+            if let Some(profile) = profile {
+                for line in profile.to_text().lines() {
+                    report.push_str("\n    ");
+                    report.push_str(line);
+                }
+            }
+            report
+        }
+        Format::Json => format!(
+            "{{\"year\":{},\"day\":{},\"part\":{},\"runs\":{},\"timings\":{{\"min_us\":{},\"median_us\":{},\"mean_us\":{}}}{}}}",
+            year,
+            day,
+            part,
+            timings.runs,
+            timings.min.as_micros(),
+            timings.median.as_micros(),
+            timings.mean.as_micros(),
+            profile.map_or(String::new(), |profile| format!(
+                ",\"profile\":{}",
+                profile.to_json()
+            ))
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::thread;
+
+    use clap::Parser;
+
+    use super::*;
+
+    fn settings(runs: usize, warmup: usize, timeout: Option<Duration>) -> Settings {
+        Settings {
+            runs,
+            warmup,
+            timeout,
+            format: Format::Text,
+        }
+    }
+
+    #[test]
+    fn test_measure() {
+        let mut calls = 0;
+        let timings = measure(&settings(5, 2, None), || {
+            calls += 1;
+            Ok(())
+        })
+        .unwrap();
+        assert_eq!(calls, 7);
+        assert_eq!(timings.runs, 5);
+        assert!(timings.min <= timings.median);
+
+        let mut calls = 0;
+        let failed = measure(&settings(5, 0, None), || {
+            calls += 1;
+            Err("Invalid input".into())
+        });
+        assert_eq!(failed, Err("Invalid input".into()));
+        assert_eq!(calls, 1);
+
+        // A part that runs past the timeout still gets one timed run.
+        let timings = measure(&settings(5, 0, Some(Duration::ZERO)), || {
+            thread::sleep(Duration::from_millis(1));
+            Ok(())
+        })
+        .unwrap();
+        assert_eq!(timings.runs, 1);
+    }
+
+    #[test]
+    fn test_settings() {
+        #[derive(Parser)]
+        struct Cli {
+            #[command(flatten)]
+            bench: BenchArgs,
+        }
+        let args = |flags: &[&str]| Cli::parse_from(["bench"].iter().chain(flags)).bench;
+        let config =
+            Config::parse("[output]\nformat = json\n[bench]\nruns = 20\ntimeout = 30\n").unwrap();
+
+        assert_eq!(
+            Settings::new(&args(&[]), &config),
+            Ok(Settings {
+                runs: 20,
+                warmup: DEFAULT_WARMUP,
+                timeout: Some(Duration::from_secs(30)),
+                format: Format::Json,
+            })
+        );
+        // Flags win over the config file.
+        assert_eq!(
+            Settings::new(
+                &args(&["--runs", "3", "--timeout", "5", "--format", "text"]),
+                &config
+            ),
+            Ok(Settings {
+                runs: 3,
+                warmup: DEFAULT_WARMUP,
+                timeout: Some(Duration::from_secs(5)),
+                format: Format::Text,
+            })
+        );
+    }
+
+    #[test]
+    fn test_report() {
+        let timings = Timings {
+            runs: 3,
+            min: Duration::from_micros(1500),
+            median: Duration::from_micros(2000),
+            mean: Duration::from_micros(2500),
+        };
+        assert_eq!(
+            report(Format::Text, (2022, 6, 1), &timings, None),
+            "2022 day  6 part 1  min      1.5ms  median      2.0ms  mean      2.5ms  (3 runs)"
+        );
+        assert_eq!(
+            report(Format::Json, (2022, 6, 1), &timings, None),
+            "{\"year\":2022,\"day\":6,\"part\":1,\"runs\":3,\"timings\":{\"min_us\":1500,\"median_us\":2000,\"mean_us\":2500}}"
+        );
+
+        // Allocations are shown alongside the timings.
+        let profile = Profile {
+            parse: None,
+            solve: allocations::Phase {
+                time: Duration::from_micros(1000),
+                allocations: allocations::Allocations {
+                    count: 2,
+                    bytes: 64,
+                    peak_bytes: 48,
+                },
+            },
+        };
+        assert_eq!(
+            report(Format::Text, (2022, 6, 1), &timings, Some(&profile)),
+            "\
+2022 day  6 part 1  min      1.5ms  median      2.0ms  mean      2.5ms  (3 runs)
+    parse  (parsed while solving)
+    solve       1.0ms         2 allocations        64 B allocated        48 B peak"
+        );
+        assert!(report(Format::Json, (2022, 6, 1), &timings, Some(&profile)).ends_with(
+            ",\"profile\":{\"parse\":null,\"solve\":{\"time_us\":1000,\"allocations\":2,\"bytes\":64,\"peak_bytes\":48}}}"
+        ));
+    }
+}
@@ -0,0 +1,303 @@
+use std::{
+    collections::BTreeMap,
+    env, fs,
+    io::ErrorKind,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use crate::{inputs, output::Format};
+
+// Defaults for command-line flags, read from an optional INI-style file:
+//
+//     # Comments start with # or ;
+//     [inputs]
+//     dir = ../inputs
+//
+//     [output]
+//     format = json
+//
+//     [bench]
+//     runs = 20
+//     warmup = 2
+//     # Stop timing a part after this many seconds (after at least one run).
+//     timeout = 30
+//
+//     # Solver parameters for every day, for day 15 of any year, and for day
+//     # 15 of 2022 only. More specific sections win.
+//     [params]
+//     [params.day15]
+//     row = 10
+//     [params.2022.day15]
+//     search_area = 20
+//
+// Flags given on the command line always win over the config file.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Config {
+    sections: BTreeMap<String, BTreeMap<String, String>>,
+}
+
+// Looked up in the current directory, then in the user's config directory.
+const PROJECT_CONFIG: &str = "advent.ini";
+const USER_CONFIG: &str = "advent/config.ini";
+
+const KNOWN_KEYS: &[(&str, &[&str])] = &[
+    ("inputs", &["dir"]),
+    ("output", &["format"]),
+    ("bench", &["runs", "warmup", "timeout"]),
+];
+
+impl Config {
+    // Loads the given config file, or else the first config file found in the
+    // usual places. Having no config file at all is fine.
+    pub fn load(path: Option<&Path>) -> Result<Config, String> {
+        let candidates = match path {
+            Some(path) => vec![path.to_path_buf()],
+            None => [
+                Some(PathBuf::from(PROJECT_CONFIG)),
+                user_config_dir().map(|dir| dir.join(USER_CONFIG)),
+            ]
+            .into_iter()
+            .flatten()
+            .collect(),
+        };
+        for candidate in candidates {
+            match fs::read_to_string(&candidate) {
+                Ok(text) => {
+                    return Config::parse(&text).map_err(|err| {
+                        format!("Invalid config file {}: {}", candidate.display(), err)
+                    })
+                }
+                Err(err) if err.kind() == ErrorKind::NotFound && path.is_none() => continue,
+                Err(err) => {
+                    return Err(format!(
+                        "Could not read config file {}: {}",
+                        candidate.display(),
+                        err
+                    ))
+                }
+            }
+        }
+        Ok(Config::default())
+    }
+
+    pub fn parse(text: &str) -> Result<Config, String> {
+        let mut config = Config::default();
+        let mut section = None;
+        for (i, line) in text.lines().enumerate() {
+            let line = line.trim();
+            let fail = |message: String| format!("line {}: {}", i + 1, message);
+            if line.is_empty() || line.starts_with('#') || line.starts_with(';') {
+                continue;
+            }
+            if let Some(name) = line
+                .strip_prefix('[')
+                .and_then(|line| line.strip_suffix(']'))
+            {
+                let name = name.trim();
+                if !is_known_section(name) {
+                    return Err(fail(format!("unknown section [{}]", name)));
+                }
+                config.sections.entry(name.to_string()).or_default();
+                section = Some(name.to_string());
+                continue;
+            }
+            let Some((key, value)) = line.split_once('=') else {
+                return Err(fail(format!(
+                    "expected [section] or key = value, found {:?}",
+                    line
+                )));
+            };
+            let (key, value) = (key.trim(), value.trim());
+            let Some(section) = &section else {
+                return Err(fail(format!("{} is not in a section", key)));
+            };
+            let known = KNOWN_KEYS
+                .iter()
+                .find(|(name, _)| name == section)
+                .is_none_or(|(_, keys)| keys.contains(&key));
+            if key.is_empty() || !known {
+                return Err(fail(format!("unknown key {:?} in [{}]", key, section)));
+            }
+            config
+                .sections
+                .entry(section.clone())
+                .or_default()
+                .insert(key.to_string(), value.to_string());
+        }
+        Ok(config)
+    }
+
+    // A setting's value from its flag, if given, or else from the config file.
+    // A flag wins even over an invalid value in the config file.
+    fn get<T>(&self, flag: Option<T>, section: &str, key: &str) -> Result<Option<T>, String>
+    where
+        T: FromStr,
+        T::Err: ToString,
+    {
+        if flag.is_some() {
+            return Ok(flag);
+        }
+        self.sections
+            .get(section)
+            .and_then(|keys| keys.get(key))
+            .map(|value| {
+                value.parse().map_err(|err: T::Err| {
+                    format!(
+                        "Invalid config value for {} in [{}]: {:?}: {}",
+                        key,
+                        section,
+                        value,
+                        err.to_string()
+                    )
+                })
+            })
+            .transpose()
+    }
+
+    pub fn inputs_dir(&self) -> PathBuf {
+        self.sections
+            .get("inputs")
+            .and_then(|keys| keys.get("dir"))
+            .map(PathBuf::from)
+            .unwrap_or_else(|| inputs::INPUTS_DIR.into())
+    }
+
+    pub fn format(&self, flag: Option<Format>) -> Result<Option<Format>, String> {
+        self.get(flag, "output", "format")
+    }
+
+    pub fn bench_runs(&self, flag: Option<usize>) -> Result<Option<usize>, String> {
+        self.get(flag, "bench", "runs")
+    }
+
+    pub fn bench_warmup(&self, flag: Option<usize>) -> Result<Option<usize>, String> {
+        self.get(flag, "bench", "warmup")
+    }
+
+    // In seconds.
+    pub fn bench_timeout(&self, flag: Option<u64>) -> Result<Option<u64>, String> {
+        self.get(flag, "bench", "timeout")
+    }
+
+    // The parameter overrides for a day, from least to most specific, so that
+    // collecting them lets the more specific sections win.
+    pub fn params(&self, year: u32, day: u32) -> Vec<(String, String)> {
+        [
+            "params".to_string(),
+            format!("params.day{}", day),
+            format!("params.{}.day{}", year, day),
+        ]
+        .iter()
+        .filter_map(|section| self.sections.get(section))
+        .flatten()
+        .map(|(key, value)| (key.clone(), value.clone()))
+        .collect()
+    }
+}
+
+fn is_known_section(name: &str) -> bool {
+    if KNOWN_KEYS.iter().any(|(section, _)| *section == name) || name == "params" {
+        return true;
+    }
+    let is_day = |part: &str| {
+        part.strip_prefix("day")
+            .is_some_and(|day| day.parse::<u32>().is_ok())
+    };
+    match name.split('.').collect::<Vec<_>>()[..] {
+        ["params", day] => is_day(day),
+        ["params", year, day] => year.parse::<u32>().is_ok() && is_day(day),
+        _ => false,
+    }
+}
+
+fn user_config_dir() -> Option<PathBuf> {
+    env::var_os("XDG_CONFIG_HOME")
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| env::var_os("APPDATA").map(PathBuf::from))
+        .or_else(|| env::var_os("HOME").map(|home| Path::new(&home).join(".config")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE_CONFIG: &str = "
+# Shared settings
+[inputs]
+dir = ../inputs
+
+[output]
+format = json
+
+[bench]
+runs = 20
+timeout = 30
+
+[params]
+row = 1
+search_area = 2
+[params.day15]
+row = 10
+[params.2022.day15]
+search_area = 20
+; only for 2021
+[params.2021.day15]
+row = 99
+";
+
+    #[test]
+    fn test_parse() {
+        let config = Config::parse(EXAMPLE_CONFIG).unwrap();
+        assert_eq!(config.inputs_dir(), PathBuf::from("../inputs"));
+        assert_eq!(config.format(None), Ok(Some(Format::Json)));
+        assert_eq!(config.bench_runs(None), Ok(Some(20)));
+        assert_eq!(config.bench_warmup(None), Ok(None));
+        assert_eq!(config.bench_timeout(None), Ok(Some(30)));
+        let params: BTreeMap<_, _> = config.params(2022, 15).into_iter().collect();
+        assert_eq!(
+            params,
+            BTreeMap::from([
+                ("row".to_string(), "10".to_string()),
+                ("search_area".to_string(), "20".to_string())
+            ])
+        );
+        assert_eq!(config.params(2022, 1).len(), 2);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            Config::parse("[output]\nformat = json\ncolour = yes\n"),
+            Err("line 3: unknown key \"colour\" in [output]".into())
+        );
+        assert_eq!(
+            Config::parse("row = 10\n"),
+            Err("line 1: row is not in a section".into())
+        );
+        assert_eq!(
+            Config::parse("[params.day]\n"),
+            Err("line 1: unknown section [params.day]".into())
+        );
+        assert!(Config::parse("[bench]\nruns\n").is_err());
+        assert!(Config::parse("[bench]\nruns = many\n")
+            .unwrap()
+            .bench_runs(None)
+            .is_err());
+    }
+
+    #[test]
+    fn test_flags_win() {
+        let config = Config::parse(EXAMPLE_CONFIG).unwrap();
+        assert_eq!(config.format(Some(Format::Text)), Ok(Some(Format::Text)));
+        assert_eq!(config.bench_runs(Some(3)), Ok(Some(3)));
+        assert_eq!(config.bench_warmup(Some(0)), Ok(Some(0)));
+        assert_eq!(config.bench_timeout(Some(5)), Ok(Some(5)));
+
+        // Even over a value the config file gets wrong.
+        let invalid = Config::parse("[output]\nformat = xml\n").unwrap();
+        assert!(invalid.format(None).is_err());
+        assert_eq!(invalid.format(Some(Format::Json)), Ok(Some(Format::Json)));
+    }
+}
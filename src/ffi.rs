@@ -0,0 +1,252 @@
+use std::{
+    ffi::{c_char, c_int, CString},
+    ptr, slice,
+};
+
+use crate::puzzles::{self, params::Params, SolveError};
+
+// A small C ABI over the solvers, declared for C in ffi/advent.h (which
+// `test_header` checks is up to date). Strings returned through out
+// parameters are allocated here, and the caller frees them with
+// advent_free_string.
+
+// Result codes from advent_solve.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AdventStatus {
+    Ok = 0,
+    UnknownYear = 1,
+    UnknownDay = 2,
+    UnknownPart = 3,
+    // The solver failed, usually because the input is invalid. The error
+    // message says why.
+    Failed = 4,
+    // A pointer argument was null, or the input wasn't UTF-8.
+    InvalidArgument = 5,
+}
+
+const VERSION: &str = concat!(env!("CARGO_PKG_VERSION"), "\0");
+
+// Returns the library version as a static string, which must not be freed.
+#[no_mangle]
+pub extern "C" fn advent_version() -> *const c_char {
+    VERSION.as_ptr().cast()
+}
+
+/// Writes up to `capacity` of the year's day numbers to `days`, in order, and
+/// returns how many days the year has. Returns 0 for unknown years.
+///
+/// # Safety
+///
+/// `days` must point to at least `capacity` writable u32s, or be null if
+/// `capacity` is 0.
+#[no_mangle]
+pub unsafe extern "C" fn advent_days(year: u32, days: *mut u32, capacity: usize) -> usize {
+    let Some(year) = puzzles::find_year(year) else {
+        return 0;
+    };
+    if !days.is_null() {
+        for (i, day) in year.days.iter().take(capacity).enumerate() {
+            *days.add(i) = day.number;
+        }
+    }
+    year.days.len()
+}
+
+/// Solves a puzzle part for an input of `input_len` bytes. On success, sets
+/// `*answer` to the answer. Otherwise, sets `*answer` to an error message (if
+/// `answer` isn't null). Either way, the caller frees `*answer`.
+///
+/// # Safety
+///
+/// `input` must point to `input_len` readable bytes, and `answer` must be null
+/// or point to a writable `char *`.
+#[no_mangle]
+pub unsafe extern "C" fn advent_solve(
+    year: u32,
+    day: u32,
+    part: u32,
+    input: *const u8,
+    input_len: usize,
+    answer: *mut *mut c_char,
+) -> c_int {
+    let (status, message) = match solve(year, day, part, input, input_len) {
+        Ok(output) => (AdventStatus::Ok, output),
+        Err((status, message)) => (status, message),
+    };
+    if !answer.is_null() {
+        *answer = c_string(message);
+    }
+    status as c_int
+}
+
+unsafe fn solve(
+    year: u32,
+    day: u32,
+    part: u32,
+    input: *const u8,
+    input_len: usize,
+) -> Result<String, (AdventStatus, String)> {
+    if input.is_null() {
+        return Err((AdventStatus::InvalidArgument, "Input is null".into()));
+    }
+    let input = std::str::from_utf8(slice::from_raw_parts(input, input_len)).map_err(|err| {
+        (
+            AdventStatus::InvalidArgument,
+            format!("Input is not UTF-8: {}", err),
+        )
+    })?;
+
+    puzzles::solve(year, day, part, input.to_string(), &Params::new()).map_err(|err| {
+        let status = match err {
+            SolveError::UnknownYear(_) => AdventStatus::UnknownYear,
+            SolveError::UnknownDay(_) => AdventStatus::UnknownDay,
+            SolveError::UnknownPart(_, _) => AdventStatus::UnknownPart,
+            SolveError::Failed(_) => AdventStatus::Failed,
+        };
+        (status, err.to_string())
+    })
+}
+
+// C strings can't contain NUL bytes, so answers and messages are cut off at
+// the first one.
+fn c_string(s: String) -> *mut c_char {
+    let mut bytes = s.into_bytes();
+    if let Some(nul) = bytes.iter().position(|b| *b == 0) {
+        bytes.truncate(nul);
+    }
+    CString::new(bytes).map_or(ptr::null_mut(), CString::into_raw)
+}
+
+/// Frees a string returned by advent_solve. Does nothing if `s` is null.
+///
+/// # Safety
+///
+/// `s` must be null or a string returned by this library that hasn't been
+/// freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn advent_free_string(s: *mut c_char) {
+    if !s.is_null() {
+        drop(CString::from_raw(s));
+    }
+}
+
+// Renders the C header for this ABI.
+pub fn header() -> String {
+    let statuses = [
+        ("ADVENT_OK", AdventStatus::Ok),
+        ("ADVENT_UNKNOWN_YEAR", AdventStatus::UnknownYear),
+        ("ADVENT_UNKNOWN_DAY", AdventStatus::UnknownDay),
+        ("ADVENT_UNKNOWN_PART", AdventStatus::UnknownPart),
+        ("ADVENT_FAILED", AdventStatus::Failed),
+        ("ADVENT_INVALID_ARGUMENT", AdventStatus::InvalidArgument),
+    ]
+    .iter()
+    .map(|(name, status)| format!("    {} = {},\n", name, *status as c_int))
+    .collect::<String>();
+    format!(
+        "\
+/* Generated from src/ffi.rs by `UPDATE_HEADER=1 cargo test test_header`. Do not edit. */
+
+#ifndef ADVENT_H
+#define ADVENT_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern \"C\" {{
+#endif
+
+/* Result codes from advent_solve. */
+typedef enum {{
+{}}} advent_status;
+
+/* The library version. Must not be freed. */
+const char *advent_version(void);
+
+/* Writes up to capacity of the year's day numbers to days, and returns how
+ * many days the year has (0 for unknown years). */
+size_t advent_days(uint32_t year, uint32_t *days, size_t capacity);
+
+/* Solves a puzzle part. Sets *answer to the answer, or to an error message if
+ * the result isn't ADVENT_OK. Free *answer with advent_free_string. */
+int advent_solve(uint32_t year, uint32_t day, uint32_t part, const uint8_t *input,
+                 size_t input_len, char **answer);
+
+/* Frees a string returned by advent_solve. */
+void advent_free_string(char *s);
+
+#ifdef __cplusplus
+}}
+#endif
+
+#endif /* ADVENT_H */
+",
+        statuses
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, ffi::CStr, fs, path::Path};
+
+    use super::*;
+
+    fn solve(day: u32, part: u32, input: &[u8]) -> (c_int, String) {
+        let mut answer = ptr::null_mut();
+        let status =
+            unsafe { advent_solve(2022, day, part, input.as_ptr(), input.len(), &mut answer) };
+        let text = unsafe { CStr::from_ptr(answer) }
+            .to_str()
+            .unwrap()
+            .to_string();
+        unsafe { advent_free_string(answer) };
+        (status, text)
+    }
+
+    #[test]
+    fn test_solve() {
+        assert_eq!(solve(2, 1, b"A Y\nB X\nC Z\n"), (0, "15".into()));
+        assert_eq!(
+            solve(2, 3, b""),
+            (
+                AdventStatus::UnknownPart as c_int,
+                "Unknown puzzle part: day 2, part 3".into()
+            )
+        );
+        assert_eq!(solve(2, 1, b"Q Y\n").0, AdventStatus::Failed as c_int);
+        assert_eq!(
+            solve(2, 1, b"\xff").0,
+            AdventStatus::InvalidArgument as c_int
+        );
+    }
+
+    #[test]
+    fn test_days() {
+        let mut days = [0; 3];
+        let count = unsafe { advent_days(2022, days.as_mut_ptr(), days.len()) };
+        assert_eq!(count, puzzles::find_year(2022).unwrap().days.len());
+        assert_eq!(days, [1, 2, 3]);
+        assert_eq!(unsafe { advent_days(1999, ptr::null_mut(), 0) }, 0);
+    }
+
+    #[test]
+    fn test_version() {
+        let version = unsafe { CStr::from_ptr(advent_version()) };
+        assert_eq!(version.to_str(), Ok(env!("CARGO_PKG_VERSION")));
+    }
+
+    #[test]
+    fn test_header() {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("ffi/advent.h");
+        if env::var_os("UPDATE_HEADER").is_some() {
+            fs::write(&path, header()).unwrap();
+        }
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            header(),
+            "ffi/advent.h is out of date; regenerate it with UPDATE_HEADER=1 cargo test test_header"
+        );
+    }
+}
@@ -0,0 +1,100 @@
+use std::{
+    fs::File,
+    io::{BufWriter, Write},
+    path::{Path, PathBuf},
+};
+
+use clap::Args;
+
+use crate::puzzles::{
+    self,
+    image::{ColorMap, ImageOptions, Painter},
+};
+
+#[derive(Args, Debug)]
+pub struct ImageArgs {
+    /// Also draw the puzzle as a binary PPM image (or PGM, if the path ends in .pgm)
+    #[arg(long, value_name = "PATH")]
+    pub image: Option<PathBuf>,
+
+    /// Average each square of N by N cells into one pixel
+    #[arg(long, default_value_t = 1, value_name = "N", requires = "image")]
+    pub downsample: usize,
+
+    /// Colors for numeric values in the image: gray, heat or terrain
+    #[arg(long, default_value = "heat", requires = "image")]
+    pub color_map: ColorMap,
+}
+
+// Paints the image and writes it to the requested path.
+pub fn write_image(
+    painter: Painter,
+    input: String,
+    path: &Path,
+    args: &ImageArgs,
+) -> Result<(), String> {
+    let options = ImageOptions {
+        downsample: args.downsample,
+        color_map: args.color_map,
+    };
+    let image = puzzles::catch_failure(|| painter(input, &options))?;
+
+    let file = File::create(path).map_err(|err| format!("Could not create image file: {}", err))?;
+    let mut out = BufWriter::new(file);
+    let written = if path.extension().is_some_and(|extension| extension == "pgm") {
+        image.write_pgm(&mut out)
+    } else {
+        image.write_ppm(&mut out)
+    };
+    written
+        .and_then(|()| out.flush())
+        .map_err(|err| format!("Could not write image file: {}", err))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs};
+
+    use super::*;
+
+    const CRT_PROGRAM: &str = "addx 15\naddx -11\nnoop\n";
+
+    fn args(path: &Path) -> ImageArgs {
+        ImageArgs {
+            image: Some(path.to_path_buf()),
+            downsample: 1,
+            color_map: ColorMap::Gray,
+        }
+    }
+
+    #[test]
+    fn test_write_image() {
+        let painter = puzzles::image::painter(puzzles::DEFAULT_YEAR, 10, 2).unwrap();
+        let dir = env::temp_dir();
+
+        let ppm = dir.join(format!("advent2022-test-{}.ppm", std::process::id()));
+        write_image(painter, CRT_PROGRAM.into(), &ppm, &args(&ppm)).unwrap();
+        let bytes = fs::read(&ppm).unwrap();
+        fs::remove_file(&ppm).unwrap();
+        // The CRT draws one row of 40 pixels, of which the first two are lit.
+        assert!(bytes.starts_with(b"P6\n40 1\n255\n\xff\xff\xff\xff\xff\xff\x00"));
+
+        let pgm = dir.join(format!("advent2022-test-{}.pgm", std::process::id()));
+        write_image(painter, CRT_PROGRAM.into(), &pgm, &args(&pgm)).unwrap();
+        let bytes = fs::read(&pgm).unwrap();
+        fs::remove_file(&pgm).unwrap();
+        assert_eq!(bytes.len(), "P5\n40 1\n255\n".len() + 40);
+    }
+
+    #[test]
+    fn test_write_failure() {
+        let painter = puzzles::image::painter(puzzles::DEFAULT_YEAR, 10, 2).unwrap();
+        let path = Path::new("/nonexistent/directory/out.ppm");
+        assert!(write_image(painter, CRT_PROGRAM.into(), path, &args(path))
+            .unwrap_err()
+            .starts_with("Could not create image file"));
+        assert!(write_image(painter, "bogus\n".into(), path, &args(path))
+            .unwrap_err()
+            .starts_with("Could not parse puzzle input"));
+    }
+}
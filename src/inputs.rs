@@ -0,0 +1,68 @@
+use std::path::{Path, PathBuf};
+
+use itertools::Itertools;
+
+// Puzzle inputs that aren't given on the command line are looked up by year
+// and day under this directory, as inputs/2022/day1.txt or
+// inputs/2022/day01.txt.
+pub const INPUTS_DIR: &str = "inputs";
+
+fn candidates(dir: &Path, year: u32, day: u32) -> [PathBuf; 2] {
+    let year_dir = dir.join(year.to_string());
+    [
+        year_dir.join(format!("day{}.txt", day)),
+        year_dir.join(format!("day{:02}.txt", day)),
+    ]
+}
+
+// Returns the input path given on the command line, or else the first
+// candidate input file that exists.
+pub fn find_input(path: Option<&str>, dir: &Path, year: u32, day: u32) -> Result<PathBuf, String> {
+    if let Some(path) = path {
+        return Ok(path.into());
+    }
+    let candidates = candidates(dir, year, day);
+    candidates
+        .iter()
+        .find(|candidate| candidate.is_file())
+        .cloned()
+        .ok_or_else(|| {
+            format!(
+                "No puzzle input file given, and none found at {}",
+                candidates
+                    .iter()
+                    .map(|candidate| candidate.display())
+                    .join(" or ")
+            )
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{env, fs};
+
+    use super::*;
+
+    #[test]
+    fn test_find_input() {
+        let dir = env::temp_dir().join(format!("advent-inputs-test-{}", std::process::id()));
+        fs::create_dir_all(dir.join("2022")).unwrap();
+        fs::write(dir.join("2022").join("day07.txt"), "").unwrap();
+
+        let found = find_input(None, &dir, 2022, 7);
+        let missing = find_input(None, &dir, 2021, 7);
+        let given = find_input(Some("elsewhere.txt"), &dir, 2022, 7);
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(found, Ok(dir.join("2022").join("day07.txt")));
+        assert_eq!(
+            missing,
+            Err(format!(
+                "No puzzle input file given, and none found at {} or {}",
+                dir.join("2021").join("day7.txt").display(),
+                dir.join("2021").join("day07.txt").display()
+            ))
+        );
+        assert_eq!(given, Ok(PathBuf::from("elsewhere.txt")));
+    }
+}
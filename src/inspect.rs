@@ -0,0 +1,80 @@
+use std::{fs, process::exit};
+
+use clap::Args;
+
+use crate::{
+    config::Config,
+    inputs,
+    puzzles::{self, inspect::inspector},
+};
+
+#[derive(Args, Debug)]
+pub struct InspectArgs {
+    /// Year of puzzle input to inspect
+    #[arg(short, long, default_value_t = puzzles::DEFAULT_YEAR)]
+    year: u32,
+
+    /// Day number of puzzle input to inspect
+    #[arg(short, long)]
+    day: u32,
+
+    /// Path to puzzle input file [default: inputs/<YEAR>/day<DAY>.txt]
+    #[arg(short, long)]
+    input_filepath: Option<String>,
+
+    /// Print the parsed model instead of a summary
+    #[arg(long)]
+    dump: bool,
+}
+
+pub fn inspect(args: InspectArgs, config: &Config) {
+    let inspector = match (
+        puzzles::find_year(args.year),
+        puzzles::find_day(args.year, args.day),
+        inspector(args.year, args.day),
+    ) {
+        (None, _, _) => {
+            println!("Unknown puzzle year: {}", args.year);
+            exit(1)
+        }
+        (Some(_), None, _) => {
+            println!("Unknown puzzle day: {}", args.day);
+            exit(1)
+        }
+        (Some(_), Some(_), None) => {
+            println!("Inspecting day {} inputs is not supported", args.day);
+            exit(1)
+        }
+        (Some(_), Some(_), Some(inspector)) => inspector,
+    };
+
+    let input_filepath = inputs::find_input(
+        args.input_filepath.as_deref(),
+        &config.inputs_dir(),
+        args.year,
+        args.day,
+    )
+    .unwrap_or_else(|err| {
+        println!("{}", err);
+        exit(1)
+    });
+    let input = fs::read_to_string(input_filepath).unwrap_or_else(|err| {
+        println!("Could not open puzzle input file: {}", err);
+        exit(1)
+    });
+
+    let report = puzzles::catch_failure(|| {
+        if args.dump {
+            (inspector.dump)(input)
+        } else {
+            (inspector.summarize)(input).to_string()
+        }
+    });
+    match report {
+        Ok(report) => print!("{}", report),
+        Err(err) => {
+            println!("{}", err);
+            exit(1)
+        }
+    }
+}
@@ -0,0 +1,4 @@
+// The puzzle solvers, shared by the command-line tool and by other languages
+// through the C ABI.
+pub mod ffi;
+pub mod puzzles;
@@ -1,65 +1,230 @@
-use std::{fmt::Display, fs, process::exit};
+use std::{
+    fs::{self, File},
+    io::{BufWriter, Write},
+    path::{Path, PathBuf},
+    process::exit,
+};
 
-use clap::Parser;
+use advent2022::puzzles;
+use clap::{ArgAction, Parser, Subcommand};
 
-mod puzzles;
+use config::Config;
+use output::Format;
+use puzzles::{
+    params::{self, Params},
+    trace::Level,
+};
 
-/// Run Advent of Code 2022 puzzle solvers
+mod allocations;
+mod bench;
+mod config;
+mod image;
+mod inputs;
+mod inspect;
+mod output;
+mod repl;
+mod report;
+mod serve;
+mod visualize;
+
+/// Run Advent of Code puzzle solvers
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
+#[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
 struct Args {
+    #[command(subcommand)]
+    command: Option<Command>,
+
+    /// Path to config file [default: advent.ini, or advent/config.ini in the user config directory]
+    #[arg(long, global = true, value_name = "PATH")]
+    config: Option<PathBuf>,
+
+    /// Year of puzzle to run
+    #[arg(short, long, default_value_t = puzzles::DEFAULT_YEAR)]
+    year: u32,
+
     /// Day number of puzzle to run
-    #[arg(short, long)]
-    day: u32,
+    #[arg(short, long, required = true)]
+    day: Option<u32>,
 
     /// Part number of puzzle to run
-    #[arg(short, long)]
-    part: u32,
+    #[arg(short, long, required = true)]
+    part: Option<u32>,
 
-    /// Path to puzzle input file
+    /// Path to puzzle input file [default: inputs/<YEAR>/day<DAY>.txt]
     #[arg(short, long)]
-    input_filepath: String,
+    input_filepath: Option<String>,
+
+    /// Override a solver parameter, as key=value (may be repeated)
+    #[arg(long = "param", value_parser = params::parse_assignment)]
+    params: Vec<(String, String)>,
+
+    /// Print the answer as text or json [default: text]
+    #[arg(long)]
+    format: Option<Format>,
+
+    /// Also report the time taken and memory allocated to parse and to solve (needs the alloc-stats feature)
+    #[arg(long)]
+    alloc_stats: bool,
+
+    /// Trace the solver's steps: -v for an outline, -vv for every step, -vvv for everything
+    #[arg(short, long, action = ArgAction::Count, conflicts_with = "trace")]
+    verbose: u8,
+
+    /// Trace the solver's steps at a level: info, debug or trace
+    #[arg(long, value_name = "LEVEL")]
+    trace: Option<Level>,
+
+    /// Write traces to a file instead of stderr
+    #[arg(long, value_name = "PATH")]
+    trace_file: Option<PathBuf>,
+
+    #[command(flatten)]
+    visualization: visualize::VisualizeArgs,
+
+    #[command(flatten)]
+    image: image::ImageArgs,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Serve puzzle solvers over HTTP on localhost
+    Serve(serve::ServeArgs),
+
+    /// Explore a puzzle input interactively
+    Repl(repl::ReplArgs),
+
+    /// Check that a puzzle input parses and summarize it without solving it
+    Inspect(inspect::InspectArgs),
+
+    /// Time puzzle solvers over several runs
+    Bench(bench::BenchArgs),
+
+    /// Analyze a puzzle input in more detail than its answers
+    Report(report::ReportArgs),
 }
 
 fn main() {
     let args = Args::parse();
+    puzzles::install_panic_hook();
+    let config = Config::load(args.config.as_deref()).unwrap_or_else(|err| {
+        println!("{}", err);
+        exit(1)
+    });
 
+    match args.command {
+        Some(Command::Serve(serve_args)) => serve::serve(serve_args),
+        Some(Command::Repl(repl_args)) => repl::repl(repl_args, &config),
+        Some(Command::Inspect(inspect_args)) => inspect::inspect(inspect_args, &config),
+        Some(Command::Bench(bench_args)) => bench::bench(bench_args, &config),
+        Some(Command::Report(report_args)) => report::report(report_args, &config),
+        None => run(args, &config),
+    }
+}
+
+fn run(args: Args, config: &Config) {
+    let (Some(day), Some(part)) = (args.day, args.part) else {
+        unreachable!("clap requires day and part without a subcommand")
+    };
+    let year = args.year;
+    if puzzles::find_year(year).is_none() {
+        println!("Unknown puzzle year: {}", year);
+        exit(1)
+    }
+    let format = match config.format(args.format) {
+        Ok(format) => format.unwrap_or_default(),
+        Err(err) => {
+            println!("{}", err);
+            exit(1)
+        }
+    };
+
+    let input_filepath = inputs::find_input(
+        args.input_filepath.as_deref(),
+        &config.inputs_dir(),
+        year,
+        day,
+    )
+    .unwrap_or_else(|err| {
+        println!("{}", err);
+        exit(1)
+    });
     let input =
-        fs::read_to_string(&args.input_filepath).unwrap_or_else(|err: std::io::Error| -> String {
+        fs::read_to_string(input_filepath).unwrap_or_else(|err: std::io::Error| -> String {
             println!("Could not open puzzle input file: {}", err);
             exit(1)
         });
+    let params: Params = config
+        .params(year, day)
+        .into_iter()
+        .chain(args.params)
+        .collect();
+
+    if args.visualization.visualize {
+        let Some(visualizer) = puzzles::visualize::visualizer(year, day, part) else {
+            println!("Visualizing day {} part {} is not supported", day, part);
+            exit(1)
+        };
+        let played = puzzles::catch_failure(|| {
+            visualize::play(
+                visualizer(input.clone(), &params).as_mut(),
+                &args.visualization,
+            )
+        });
+        if let Err(err) = played {
+            println!("{}", err);
+            exit(1)
+        }
+    }
 
-    match args.day {
-        1 => run_day(puzzles::day1::part1, puzzles::day1::part2, args, input),
-        2 => run_day(puzzles::day2::part1, puzzles::day2::part2, args, input),
-        3 => run_day(puzzles::day3::part1, puzzles::day3::part2, args, input),
-        4 => run_day(puzzles::day4::part1, puzzles::day4::part2, args, input),
-        5 => run_day(puzzles::day5::part1, puzzles::day5::part2, args, input),
-        6 => run_day(puzzles::day6::part1, puzzles::day6::part2, args, input),
-        7 => run_day(puzzles::day7::part1, puzzles::day7::part2, args, input),
-        8 => run_day(puzzles::day8::part1, puzzles::day8::part2, args, input),
-        9 => run_day(puzzles::day9::part1, puzzles::day9::part2, args, input),
-        10 => run_day(puzzles::day10::part1, puzzles::day10::part2, args, input),
-        11 => run_day(puzzles::day11::part1, puzzles::day11::part2, args, input),
-        12 => run_day(puzzles::day12::part1, puzzles::day12::part2, args, input),
-        13 => run_day(puzzles::day13::part1, puzzles::day13::part2, args, input),
-        14 => run_day(puzzles::day14::part1, puzzles::day14::part2, args, input),
-        15 => run_day(puzzles::day15::part1, puzzles::day15::part2, args, input),
-        _ => println!("Unknown puzzle day: {}", args.day),
+    if let Some(path) = &args.image.image {
+        let Some(painter) = puzzles::image::painter(year, day, part) else {
+            println!("Drawing day {} part {} is not supported", day, part);
+            exit(1)
+        };
+        if let Err(err) = image::write_image(painter, input.clone(), path, &args.image) {
+            println!("{}", err);
+            exit(1)
+        }
+    }
+
+    // Trace only the solver, and not the visualization or image, which run
+    // the same steps.
+    if let Some(level) = args.trace.or(Level::from_verbosity(args.verbose)) {
+        start_tracing(level, args.trace_file.as_deref());
+    }
+    let solved = if args.alloc_stats && allocations::available() {
+        allocations::profile(year, day, part, &input, &params)
+            .map(|(answer, profile)| (answer, Some(profile)))
+    } else {
+        puzzles::solve(year, day, part, input, &params).map(|answer| (answer, None))
+    };
+    match solved {
+        Ok((answer, profile)) => println!(
+            "{}",
+            output::answer(format, (year, day, part), &answer, profile.as_ref())
+        ),
+        Err(err @ puzzles::SolveError::Failed(_)) => {
+            println!("{}", err);
+            exit(1)
+        }
+        Err(err) => println!("{}", err),
     }
 }
 
-fn run_day<F1, F2, R1, R2>(part1: F1, part2: F2, args: Args, input: String)
-where
-    R1: Display,
-    R2: Display,
-    F1: Fn(String) -> R1,
-    F2: Fn(String) -> R2,
-{
-    match args.part {
-        1 => println!("{}", part1(input)),
-        2 => println!("{}", part2(input)),
-        _ => println!("Unknown puzzle part: day {}, part {}", args.day, args.part),
+fn start_tracing(level: Level, path: Option<&Path>) {
+    if !cfg!(feature = "trace") {
+        eprintln!("Tracing is not available: built without the trace feature");
+        return;
     }
+    let sink = path.map(|path| -> Box<dyn Write + Send> {
+        match File::create(path) {
+            Ok(file) => Box::new(BufWriter::new(file)),
+            Err(err) => {
+                println!("Could not create trace file: {}", err);
+                exit(1)
+            }
+        }
+    });
+    puzzles::trace::enable(Some(level), sink);
 }
@@ -0,0 +1,88 @@
+use std::str::FromStr;
+
+use crate::allocations::Profile;
+
+// How answers (and benchmark results) are printed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Format {
+    #[default]
+    Text,
+    // One JSON object per line.
+    Json,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            _ => Err(format!(
+                "Unknown output format {:?} (expected text or json)",
+                s
+            )),
+        }
+    }
+}
+
+pub fn json_string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+// The answer, followed by where its time and memory went if it was profiled.
+pub fn answer(
+    format: Format,
+    (year, day, part): (u32, u32, u32),
+    answer: &str,
+    profile: Option<&Profile>,
+) -> String {
+    match (format, profile) {
+        (Format::Text, None) => answer.to_string(),
+        (Format::Text, Some(profile)) => format!("{}\n{}", answer, profile.to_text()),
+        (Format::Json, _) => format!(
+            "{{\"year\":{},\"day\":{},\"part\":{},\"answer\":{}{}}}",
+            year,
+            day,
+            part,
+            json_string(answer),
+            profile.map_or(String::new(), |profile| format!(
+                ",\"profile\":{}",
+                profile.to_json()
+            ))
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_json_string() {
+        assert_eq!(json_string("##.\n\"a\""), "\"##.\\n\\\"a\\\"\"");
+    }
+
+    #[test]
+    fn test_answer() {
+        assert_eq!(answer(Format::Text, (2022, 6, 1), "7", None), "7");
+        assert_eq!(
+            answer(Format::Json, (2022, 10, 2), "#.\n.#", None),
+            "{\"year\":2022,\"day\":10,\"part\":2,\"answer\":\"#.\\n.#\"}"
+        );
+    }
+}
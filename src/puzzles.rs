@@ -1,17 +1,107 @@
-mod shared;
-
-pub mod day1;
-pub mod day2;
-pub mod day3;
-pub mod day4;
-pub mod day5;
-pub mod day6;
-pub mod day7;
-pub mod day8;
-pub mod day9;
-pub mod day10;
-pub mod day11;
-pub mod day12;
-pub mod day13;
-pub mod day14;
-pub mod day15;
+pub mod explore;
+pub mod image;
+pub mod inspect;
+pub mod params;
+pub mod report;
+// Helpers for writing solvers. Public so that they can be used (and tested)
+// before any day needs them.
+pub mod shared;
+pub mod visualize;
+
+pub mod year2022;
+
+use params::Params;
+// Solvers, parsers and every hook in `Year` fail the same way on an invalid
+// input: they stop with `fail!` (or `checked!`, on arithmetic overflow), and
+// callers run them through `catch_failure` to get the failure as a message.
+pub use shared::{catch_failure, install_panic_hook, trace};
+
+pub type Solver = fn(String, &Params) -> String;
+
+pub struct Day {
+    pub number: u32,
+    // Runs just the day's input parser, for days that parse their input
+    // before solving it.
+    pub parse: Option<fn(&str)>,
+    pub part1: Solver,
+    pub part2: Solver,
+}
+
+// The hooks a year provides for its days, besides solving them. Each returns
+// None for days (or parts) that don't support the hook.
+pub struct Year {
+    pub number: u32,
+    pub days: &'static [Day],
+    pub explorer: fn(u32) -> Option<explore::Explorer>,
+    pub inspector: fn(u32) -> Option<inspect::Inspector>,
+    pub reporter: fn(u32) -> Option<report::Reporter>,
+    pub visualizer: fn(u32, u32) -> Option<visualize::Visualizer>,
+    pub painter: fn(u32, u32) -> Option<image::Painter>,
+}
+
+pub static YEARS: &[Year] = &[Year {
+    number: 2022,
+    days: year2022::DAYS,
+    explorer: year2022::explorer,
+    inspector: year2022::inspector,
+    reporter: year2022::reporter,
+    visualizer: year2022::visualizer,
+    painter: year2022::painter,
+}];
+
+pub const DEFAULT_YEAR: u32 = 2022;
+
+pub fn find_year(number: u32) -> Option<&'static Year> {
+    YEARS.iter().find(|year| year.number == number)
+}
+
+pub fn find_day(year: u32, number: u32) -> Option<&'static Day> {
+    find_year(year)?
+        .days
+        .iter()
+        .find(|day| day.number == number)
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum SolveError {
+    UnknownYear(u32),
+    UnknownDay(u32),
+    UnknownPart(u32, u32),
+    // The solver failed, either because the puzzle input was invalid or
+    // because of a bug in the solver.
+    Failed(String),
+}
+
+impl std::fmt::Display for SolveError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SolveError::UnknownYear(year) => write!(f, "Unknown puzzle year: {}", year),
+            SolveError::UnknownDay(day) => write!(f, "Unknown puzzle day: {}", day),
+            SolveError::UnknownPart(day, part) => {
+                write!(f, "Unknown puzzle part: day {}, part {}", day, part)
+            }
+            SolveError::Failed(message) => write!(f, "{}", message),
+        }
+    }
+}
+
+// Runs the solver for a puzzle part, catching solver failures so that the
+// caller can report them.
+pub fn solve(
+    year: u32,
+    day: u32,
+    part: u32,
+    input: String,
+    params: &Params,
+) -> Result<String, SolveError> {
+    if find_year(year).is_none() {
+        return Err(SolveError::UnknownYear(year));
+    }
+    let solver = match (find_day(year, day), part) {
+        (None, _) => return Err(SolveError::UnknownDay(day)),
+        (Some(day), 1) => day.part1,
+        (Some(day), 2) => day.part2,
+        (Some(_), _) => return Err(SolveError::UnknownPart(day, part)),
+    };
+    shared::catch_failure(|| solver(input, params)).map_err(SolveError::Failed)
+}
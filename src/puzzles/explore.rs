@@ -0,0 +1,26 @@
+// An interactive view over a day's parsed puzzle input, used by the REPL to
+// answer questions that the puzzle parts don't ask.
+pub trait Explore {
+    // Lists the day's commands as (usage, description) pairs.
+    fn commands(&self) -> Vec<(&'static str, &'static str)>;
+
+    // Runs one of the day's commands. Returns None if the command isn't one of
+    // the day's commands, and an error if its arguments are invalid.
+    fn run(&self, command: &str, args: &[&str]) -> Option<Result<String, String>>;
+}
+
+pub type Explorer = fn(String) -> Box<dyn Explore>;
+
+// Returns the explorer for days that have one. Explorers parse their input
+// when constructed.
+pub fn explorer(year: u32, day: u32) -> Option<Explorer> {
+    (super::find_year(year)?.explorer)(day)
+}
+
+// Checks that a command received exactly as many arguments as its usage needs.
+pub fn expect_args<'a, const N: usize>(
+    args: &[&'a str],
+    usage: &str,
+) -> Result<[&'a str; N], String> {
+    <[&str; N]>::try_from(args).map_err(|_| format!("Usage: {}", usage))
+}
@@ -0,0 +1,10 @@
+pub use super::shared::image::{ColorMap, Image, ImageOptions};
+
+// Draws a puzzle part as an image, usually the grid it works on and what it
+// found there.
+pub type Painter = fn(String, &ImageOptions) -> Image;
+
+// Returns the painter for puzzle parts that have one.
+pub fn painter(year: u32, day: u32, part: u32) -> Option<Painter> {
+    (super::find_year(year)?.painter)(day, part)
+}
@@ -0,0 +1,40 @@
+use std::fmt::{self, Display, Formatter};
+
+// A structured description of a parsed puzzle input, as a list of named
+// fields in the order they should be shown.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Summary {
+    fields: Vec<(&'static str, String)>,
+}
+
+impl Summary {
+    pub fn new() -> Summary {
+        Summary::default()
+    }
+
+    pub fn field<T: Display>(mut self, name: &'static str, value: T) -> Summary {
+        self.fields.push((name, value.to_string()));
+        self
+    }
+}
+
+impl Display for Summary {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let width = self.fields.iter().map(|(name, _)| name.len()).max();
+        for (name, value) in &self.fields {
+            writeln!(f, "{:width$}  {}", name, value, width = width.unwrap_or(0))?;
+        }
+        Ok(())
+    }
+}
+
+// Both functions parse the input with the day's own parser.
+pub struct Inspector {
+    pub summarize: fn(String) -> Summary,
+    // Renders the parsed model with its Debug representation.
+    pub dump: fn(String) -> String,
+}
+
+pub fn inspector(year: u32, day: u32) -> Option<Inspector> {
+    (super::find_year(year)?.inspector)(day)
+}
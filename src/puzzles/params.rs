@@ -0,0 +1,82 @@
+use std::{collections::BTreeMap, fmt::Display, str::FromStr};
+
+use super::shared::fail;
+
+// Named overrides for the constants that a solver would otherwise hard-code
+// (like the target row in day 15). Solvers read them with a default, so an
+// empty set of parameters always produces the puzzle's own answer.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Params(BTreeMap<String, String>);
+
+impl Params {
+    pub fn new() -> Params {
+        Params::default()
+    }
+
+    pub fn insert(&mut self, key: &str, value: &str) {
+        self.0.insert(key.to_string(), value.to_string());
+    }
+
+    pub fn remove(&mut self, key: &str) -> bool {
+        self.0.remove(key).is_some()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.0
+            .iter()
+            .map(|(key, value)| (key.as_str(), value.as_str()))
+    }
+
+    pub fn get<T>(&self, key: &str, default: T) -> T
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        match self.0.get(key) {
+            Some(value) => value
+                .parse()
+                .unwrap_or_else(|err| fail!("Invalid value for parameter {key}: {value:?}: {err}")),
+            None => default,
+        }
+    }
+}
+
+impl FromIterator<(String, String)> for Params {
+    fn from_iter<I: IntoIterator<Item = (String, String)>>(iter: I) -> Self {
+        Params(iter.into_iter().collect())
+    }
+}
+
+// Parses a `key=value` parameter assignment.
+pub fn parse_assignment(assignment: &str) -> Result<(String, String), String> {
+    match assignment.split_once('=') {
+        Some((key, value)) if !key.is_empty() => Ok((key.to_string(), value.to_string())),
+        _ => Err(format!(
+            "expected a parameter assignment of the form key=value, got {assignment:?}"
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_get() {
+        let params: Params = [("row".to_string(), "10".to_string())]
+            .into_iter()
+            .collect();
+        assert_eq!(params.get("row", 2_000_000), 10);
+        assert_eq!(params.get("search_area", 4_000_000), 4_000_000);
+    }
+
+    #[test]
+    fn test_parse_assignment() {
+        assert_eq!(
+            parse_assignment("row=10"),
+            Ok(("row".to_string(), "10".to_string()))
+        );
+        assert!(parse_assignment("row").is_err());
+        assert!(parse_assignment("=10").is_err());
+    }
+}
@@ -0,0 +1,10 @@
+use super::params::Params;
+
+// Writes a detailed analysis of a puzzle input, answering questions that the
+// puzzle parts don't ask. Reports parse their input with the day's own
+// parser and, like solvers, can take parameters.
+pub type Reporter = fn(String, &Params) -> String;
+
+pub fn reporter(year: u32, day: u32) -> Option<Reporter> {
+    (super::find_year(year)?.reporter)(day)
+}
@@ -1,23 +1,150 @@
-use std::process::exit;
+use std::{
+    any::Any,
+    cell::RefCell,
+    panic::{self, AssertUnwindSafe},
+    sync::Once,
+};
 
-use nom::IResult;
+pub mod geometry;
+pub mod grid;
+pub mod image;
+pub mod parsing;
+pub mod range_set;
+pub mod trace;
 
-pub fn must_parse<'a, F, T>(parser: F, input: &'a str) -> T
+pub use parsing::must_parse;
+
+// Aborts the current solver run. Takes the same arguments as `format!`.
+macro_rules! fail {
+    ($($arg:tt)*) => {
+        $crate::puzzles::shared::abort_solver(format!($($arg)*))
+    };
+}
+pub(crate) use fail;
+
+// Emits a trace event, with a level, an event name and any number of
+// `key = value` fields whose values are Display. The fields are only
+// evaluated when tracing at that level, and without the trace feature the
+// whole event compiles to nothing.
+macro_rules! trace_event {
+    ($level:ident, $event:literal $(, $key:ident = $value:expr)* $(,)?) => {
+        #[cfg(feature = "trace")]
+        {
+            use $crate::puzzles::shared::trace::{emit, enabled, Level};
+            if enabled(Level::$level) {
+                emit(
+                    Level::$level,
+                    module_path!(),
+                    $event,
+                    &[$((stringify!($key), &$value as &dyn ::std::fmt::Display)),*],
+                )
+            }
+        }
+        // Still refer to the fields, so that values only used by traces don't
+        // look unused.
+        #[cfg(not(feature = "trace"))]
+        if false {
+            $(let _ = &$value;)*
+        }
+    };
+}
+// Named so that `use shared::trace` brings in both the macro and the module.
+pub(crate) use trace_event as trace;
+
+// Unwraps the result of a checked arithmetic operation, like
+// `a.checked_add(b)`, failing the solver if it overflowed. The rest of the
+// arguments describe the operation for the error, the same way as `format!`,
+// and are only formatted on overflow.
+macro_rules! checked {
+    ($value:expr, $($description:tt)*) => {
+        match $value {
+            Some(value) => value,
+            None => $crate::puzzles::shared::overflow(module_path!(), format!($($description)*)),
+        }
+    };
+}
+pub(crate) use checked;
+
+// Solvers report invalid puzzles by unwinding with an InvalidPuzzle payload
+// rather than exiting the process, so that callers who run many solvers in one
+// process (like the HTTP server) can recover and report the error.
+#[derive(Debug)]
+pub struct InvalidPuzzle(pub String);
+
+pub fn abort_solver(message: String) -> ! {
+    panic::panic_any(InvalidPuzzle(message))
+}
+
+// Fails the solver with an error naming the puzzle that overflowed, given the
+// module that did the arithmetic.
+pub fn overflow(module: &str, operation: String) -> ! {
+    abort_solver(format!(
+        "Overflow in {}: {}",
+        trace::source(module),
+        operation
+    ))
+}
+
+thread_local! {
+    // The puzzle whose code last overflowed on this thread, recorded by the
+    // panic hook, since overflow panics only say which operation overflowed.
+    static OVERFLOWED_IN: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+// Installs a panic hook that keeps quiet about InvalidPuzzle failures and
+// overflows, since whoever catches them is responsible for reporting them.
+// Only the first call installs it.
+pub fn install_panic_hook() {
+    static INSTALL: Once = Once::new();
+    INSTALL.call_once(|| {
+        let default_hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            let overflowed = info.payload_as_str().is_some_and(is_overflow_panic);
+            if overflowed {
+                let puzzle = info
+                    .location()
+                    .and_then(|location| trace::puzzle(location.file()));
+                OVERFLOWED_IN.with(|overflowed_in| *overflowed_in.borrow_mut() = puzzle);
+            }
+            if !info.payload().is::<InvalidPuzzle>() && !overflowed {
+                default_hook(info)
+            }
+        }));
+    });
+}
+
+// Runs a solver, converting any failure (invalid puzzles as well as solver
+// bugs) into an error message. This is the one place failures are turned into
+// messages, so every way of running a solver or hook reports them alike.
+pub fn catch_failure<F, T>(f: F) -> Result<T, String>
 where
-    F: Fn(&'a str) -> IResult<&'a str, T>,
+    F: FnOnce() -> T,
 {
-    match parser(input) {
-        Ok(("", pairs)) => pairs,
-        Ok((remaining, _)) => {
-            println!(
-                "Invalid puzzle input: could not parse input suffix: {}",
-                remaining
-            );
-            exit(1)
-        }
-        Err(err) => {
-            println!("Could not parse puzzle input: {}", err);
-            exit(1)
+    install_panic_hook();
+    panic::catch_unwind(AssertUnwindSafe(f)).map_err(failure_message)
+}
+
+fn failure_message(payload: Box<dyn Any + Send>) -> String {
+    if let Some(InvalidPuzzle(message)) = payload.downcast_ref::<InvalidPuzzle>() {
+        message.clone()
+    } else if let Some(message) = payload.downcast_ref::<&str>() {
+        if is_overflow_panic(message) {
+            match OVERFLOWED_IN.with(|overflowed_in| overflowed_in.borrow_mut().take()) {
+                Some(puzzle) => format!("Overflow in {}: {}", puzzle, message),
+                None => format!("Overflow: {}", message),
+            }
+        } else {
+            format!("Solver panicked: {}", message)
         }
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        format!("Solver panicked: {}", message)
+    } else {
+        "Solver panicked".into()
     }
 }
+
+// Builds with overflow checks (like the `checked` profile) panic on any
+// arithmetic overflow, with messages like "attempt to add with overflow".
+fn is_overflow_panic(message: &str) -> bool {
+    message.starts_with("attempt to ") && message.ends_with(" with overflow")
+}
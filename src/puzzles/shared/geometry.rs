@@ -0,0 +1,333 @@
+use std::{
+    fmt::{self, Display, Formatter},
+    ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign},
+};
+
+// Coordinates follow the screen convention used by most puzzles: x grows
+// rightwards and y grows downwards.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Point {
+    pub x: i32,
+    pub y: i32,
+}
+
+// The difference between two points.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+pub struct Vector {
+    pub dx: i32,
+    pub dy: i32,
+}
+
+impl Point {
+    pub const ORIGIN: Point = Point { x: 0, y: 0 };
+
+    pub const fn new(x: i32, y: i32) -> Point {
+        Point { x, y }
+    }
+
+    pub fn manhattan(self, other: Point) -> i32 {
+        (self - other).manhattan_len()
+    }
+
+    // The number of king moves between two points, where diagonal steps count
+    // as one.
+    pub fn chebyshev(self, other: Point) -> i32 {
+        (self - other).chebyshev_len()
+    }
+
+    // Converts to unsigned (x, y) coordinates, like grid positions. Returns
+    // None if either coordinate is negative.
+    pub fn to_unsigned(self) -> Option<(usize, usize)> {
+        Some((usize::try_from(self.x).ok()?, usize::try_from(self.y).ok()?))
+    }
+
+    // Converts from unsigned (x, y) coordinates. Returns None if either
+    // coordinate is too large.
+    pub fn from_unsigned((x, y): (usize, usize)) -> Option<Point> {
+        Some(Point::new(i32::try_from(x).ok()?, i32::try_from(y).ok()?))
+    }
+}
+
+impl Vector {
+    pub const ZERO: Vector = Vector { dx: 0, dy: 0 };
+
+    pub const fn new(dx: i32, dy: i32) -> Vector {
+        Vector { dx, dy }
+    }
+
+    pub fn manhattan_len(self) -> i32 {
+        self.dx.abs() + self.dy.abs()
+    }
+
+    pub fn chebyshev_len(self) -> i32 {
+        self.dx.abs().max(self.dy.abs())
+    }
+
+    // Clamps each component to -1, 0 or 1: the single (possibly diagonal)
+    // step that goes in this vector's direction.
+    pub fn signum(self) -> Vector {
+        Vector::new(self.dx.signum(), self.dy.signum())
+    }
+}
+
+// The eight single steps to neighboring points, including diagonals.
+pub const NEIGHBORS_8: [Vector; 8] = [
+    Vector::new(0, -1),
+    Vector::new(0, 1),
+    Vector::new(-1, 0),
+    Vector::new(1, 0),
+    Vector::new(-1, -1),
+    Vector::new(1, -1),
+    Vector::new(-1, 1),
+    Vector::new(1, 1),
+];
+
+impl From<(i32, i32)> for Point {
+    fn from((x, y): (i32, i32)) -> Point {
+        Point::new(x, y)
+    }
+}
+
+impl Display for Point {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{},{}", self.x, self.y)
+    }
+}
+
+impl Add<Vector> for Point {
+    type Output = Point;
+
+    fn add(self, rhs: Vector) -> Point {
+        Point::new(self.x + rhs.dx, self.y + rhs.dy)
+    }
+}
+
+impl AddAssign<Vector> for Point {
+    fn add_assign(&mut self, rhs: Vector) {
+        *self = *self + rhs
+    }
+}
+
+impl Sub<Vector> for Point {
+    type Output = Point;
+
+    fn sub(self, rhs: Vector) -> Point {
+        self + -rhs
+    }
+}
+
+impl SubAssign<Vector> for Point {
+    fn sub_assign(&mut self, rhs: Vector) {
+        *self = *self - rhs
+    }
+}
+
+impl Sub for Point {
+    type Output = Vector;
+
+    fn sub(self, rhs: Point) -> Vector {
+        Vector::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl Add for Vector {
+    type Output = Vector;
+
+    fn add(self, rhs: Vector) -> Vector {
+        Vector::new(self.dx + rhs.dx, self.dy + rhs.dy)
+    }
+}
+
+impl Sub for Vector {
+    type Output = Vector;
+
+    fn sub(self, rhs: Vector) -> Vector {
+        self + -rhs
+    }
+}
+
+impl Neg for Vector {
+    type Output = Vector;
+
+    fn neg(self) -> Vector {
+        Vector::new(-self.dx, -self.dy)
+    }
+}
+
+impl Mul<i32> for Vector {
+    type Output = Vector;
+
+    fn mul(self, rhs: i32) -> Vector {
+        Vector::new(self.dx * rhs, self.dy * rhs)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+use Direction::*;
+
+impl Direction {
+    pub const ALL: [Direction; 4] = [Up, Down, Left, Right];
+
+    // Parses the U/D/L/R letters used by most puzzles.
+    pub fn from_letter(c: char) -> Option<Direction> {
+        match c {
+            'U' => Some(Up),
+            'D' => Some(Down),
+            'L' => Some(Left),
+            'R' => Some(Right),
+            _ => None,
+        }
+    }
+
+    // Parses the ^/v/</> arrows used by map puzzles.
+    pub fn from_arrow(c: char) -> Option<Direction> {
+        match c {
+            '^' => Some(Up),
+            'v' => Some(Down),
+            '<' => Some(Left),
+            '>' => Some(Right),
+            _ => None,
+        }
+    }
+
+    pub fn vector(self) -> Vector {
+        match self {
+            Up => Vector::new(0, -1),
+            Down => Vector::new(0, 1),
+            Left => Vector::new(-1, 0),
+            Right => Vector::new(1, 0),
+        }
+    }
+
+    // Turns 90 degrees counter-clockwise, as seen on screen.
+    pub fn turn_left(self) -> Direction {
+        match self {
+            Up => Left,
+            Left => Down,
+            Down => Right,
+            Right => Up,
+        }
+    }
+
+    // Turns 90 degrees clockwise, as seen on screen.
+    pub fn turn_right(self) -> Direction {
+        self.turn_left().reverse()
+    }
+
+    pub fn reverse(self) -> Direction {
+        match self {
+            Up => Down,
+            Down => Up,
+            Left => Right,
+            Right => Left,
+        }
+    }
+}
+
+// An inclusive axis-aligned box.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BoundingBox {
+    pub min: Point,
+    pub max: Point,
+}
+
+impl BoundingBox {
+    pub fn new(point: Point) -> BoundingBox {
+        BoundingBox {
+            min: point,
+            max: point,
+        }
+    }
+
+    // The smallest box containing every point, or None if there are no
+    // points.
+    pub fn of<I: IntoIterator<Item = Point>>(points: I) -> Option<BoundingBox> {
+        let mut points = points.into_iter();
+        let first = points.next()?;
+        Some(points.fold(BoundingBox::new(first), BoundingBox::including))
+    }
+
+    pub fn including(self, point: Point) -> BoundingBox {
+        BoundingBox {
+            min: Point::new(self.min.x.min(point.x), self.min.y.min(point.y)),
+            max: Point::new(self.max.x.max(point.x), self.max.y.max(point.y)),
+        }
+    }
+
+    pub fn contains(&self, point: Point) -> bool {
+        (self.min.x..=self.max.x).contains(&point.x) && (self.min.y..=self.max.y).contains(&point.y)
+    }
+
+    pub fn width(&self) -> i32 {
+        self.max.x - self.min.x + 1
+    }
+
+    pub fn height(&self) -> i32 {
+        self.max.y - self.min.y + 1
+    }
+
+    // Points in reading order: left to right, then top to bottom.
+    pub fn points(self) -> impl Iterator<Item = Point> {
+        (self.min.y..=self.max.y)
+            .flat_map(move |y| (self.min.x..=self.max.x).map(move |x| Point::new(x, y)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_arithmetic() {
+        let a = Point::new(1, 2);
+        let b = Point::new(4, -2);
+        assert_eq!(b - a, Vector::new(3, -4));
+        assert_eq!(a + (b - a), b);
+        assert_eq!(a - Vector::new(1, 1) * 2, Point::new(-1, 0));
+        assert_eq!((b - a).signum(), Vector::new(1, -1));
+    }
+
+    #[test]
+    fn test_distances() {
+        let a = Point::new(1, 2);
+        let b = Point::new(4, -2);
+        assert_eq!(a.manhattan(b), 7);
+        assert_eq!(a.chebyshev(b), 4);
+    }
+
+    #[test]
+    fn test_directions() {
+        assert_eq!(Direction::from_letter('R'), Some(Right));
+        assert_eq!(Direction::from_arrow('^'), Some(Up));
+        assert_eq!(Up.turn_right(), Right);
+        assert_eq!(Up.turn_left(), Left);
+        assert_eq!(
+            Point::ORIGIN + Left.vector() + Down.vector(),
+            Point::new(-1, 1)
+        );
+    }
+
+    #[test]
+    fn test_conversions() {
+        assert_eq!(Point::new(3, 4).to_unsigned(), Some((3, 4)));
+        assert_eq!(Point::new(-1, 4).to_unsigned(), None);
+        assert_eq!(Point::from_unsigned((3, 4)), Some(Point::new(3, 4)));
+    }
+
+    #[test]
+    fn test_bounding_box() {
+        let bounds = BoundingBox::of([Point::new(2, 0), Point::new(-1, 1)]).unwrap();
+        assert_eq!((bounds.width(), bounds.height()), (4, 2));
+        assert!(bounds.contains(Point::new(0, 1)));
+        assert!(!bounds.contains(Point::new(0, 2)));
+        assert_eq!(bounds.points().count(), 8);
+        assert_eq!(BoundingBox::of([]), None);
+    }
+}
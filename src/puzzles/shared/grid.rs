@@ -0,0 +1,336 @@
+use std::{
+    collections::HashMap,
+    ops::{Index, IndexMut},
+};
+
+use super::geometry::{BoundingBox, Direction, Point, Vector, NEIGHBORS_8};
+
+// Positions are (x, y), with the origin at the top left. Positive directions
+// are rightwards and downwards.
+pub type Position = (usize, usize);
+
+// A dense, rectangular grid. Lookups outside of the grid return None (or
+// panic, when indexing).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+
+    // Builds a grid from its cells in reading order. The number of cells must
+    // be a multiple of the width.
+    pub fn from_cells(width: usize, cells: Vec<T>) -> Grid<T> {
+        assert!(width > 0 && cells.len().is_multiple_of(width));
+        Grid {
+            width,
+            height: cells.len() / width,
+            cells,
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, (x, y): Position) -> bool {
+        x < self.width && y < self.height
+    }
+
+    pub fn is_edge(&self, (x, y): Position) -> bool {
+        self.contains((x, y)) && (x == 0 || y == 0 || x == self.width - 1 || y == self.height - 1)
+    }
+
+    pub fn get(&self, position: Position) -> Option<&T> {
+        self.index_of(position).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, position: Position) -> Option<&mut T> {
+        self.index_of(position).map(|i| &mut self.cells[i])
+    }
+
+    fn index_of(&self, position @ (x, y): Position) -> Option<usize> {
+        if self.contains(position) {
+            Some(y * self.width + x)
+        } else {
+            None
+        }
+    }
+
+    // Positions in reading order: left to right, then top to bottom.
+    pub fn positions(&self) -> impl Iterator<Item = Position> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Position, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &T> {
+        self.cells.iter()
+    }
+
+    pub fn row(&self, y: usize) -> impl Iterator<Item = Position> {
+        (0..self.width).map(move |x| (x, y))
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = Position> {
+        (0..self.height).map(move |y| (x, y))
+    }
+
+    // Positions reached by repeatedly taking a step from a position, not
+    // including the position itself, until leaving the grid.
+    pub fn ray(&self, from: Position, step: Vector) -> impl Iterator<Item = Position> + '_ {
+        let mut current = from;
+        std::iter::from_fn(move || {
+            current = self.step(current, step)?;
+            Some(current)
+        })
+    }
+
+    // The position one step away, if it's within the grid.
+    pub fn step(&self, (x, y): Position, step: Vector) -> Option<Position> {
+        let next = (
+            x.checked_add_signed(step.dx as isize)?,
+            y.checked_add_signed(step.dy as isize)?,
+        );
+        if self.contains(next) {
+            Some(next)
+        } else {
+            None
+        }
+    }
+
+    pub fn neighbors4(&self, position: Position) -> impl Iterator<Item = Position> + '_ {
+        Direction::ALL
+            .iter()
+            .filter_map(move |direction| self.step(position, direction.vector()))
+    }
+
+    pub fn neighbors8(&self, position: Position) -> impl Iterator<Item = Position> + '_ {
+        NEIGHBORS_8
+            .iter()
+            .filter_map(move |step| self.step(position, *step))
+    }
+
+    pub fn map<U, F>(&self, f: F) -> Grid<U>
+    where
+        F: FnMut(&T) -> U,
+    {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+
+    // Renders the grid as text, with one line per row.
+    pub fn render<F>(&self, mut cell: F) -> String
+    where
+        F: FnMut(Position, &T) -> char,
+    {
+        let mut rendered = String::with_capacity((self.width + 1) * self.height);
+        for (position @ (x, _), value) in self.iter() {
+            rendered.push(cell(position, value));
+            if x == self.width - 1 {
+                rendered.push('\n');
+            }
+        }
+        rendered
+    }
+}
+
+impl<T> Index<Position> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, position: Position) -> &T {
+        self.get(position)
+            .unwrap_or_else(|| panic!("position {position:?} is outside of grid"))
+    }
+}
+
+impl<T> IndexMut<Position> for Grid<T> {
+    fn index_mut(&mut self, position: Position) -> &mut T {
+        self.get_mut(position)
+            .unwrap_or_else(|| panic!("position {position:?} is outside of grid"))
+    }
+}
+
+// A sparse grid for unbounded worlds, where most cells are empty. It keeps
+// track of the bounding box of its occupied cells.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SparseGrid<T> {
+    cells: HashMap<Point, T>,
+    bounds: Option<BoundingBox>,
+}
+
+impl<T> Default for SparseGrid<T> {
+    fn default() -> Self {
+        SparseGrid {
+            cells: HashMap::new(),
+            bounds: None,
+        }
+    }
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new() -> SparseGrid<T> {
+        SparseGrid::default()
+    }
+
+    pub fn insert(&mut self, position: Point, value: T) -> Option<T> {
+        self.bounds = Some(match self.bounds {
+            None => BoundingBox::new(position),
+            Some(bounds) => bounds.including(position),
+        });
+        self.cells.insert(position, value)
+    }
+
+    pub fn get(&self, position: Point) -> Option<&T> {
+        self.cells.get(&position)
+    }
+
+    pub fn contains(&self, position: Point) -> bool {
+        self.cells.contains_key(&position)
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    // Occupied cells, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.cells
+            .iter()
+            .map(|(position, value)| (*position, value))
+    }
+
+    // The box containing every occupied cell, if any cells are occupied.
+    pub fn bounds(&self) -> Option<BoundingBox> {
+        self.bounds
+    }
+
+    // Renders the bounding box of the occupied cells (extended to include any
+    // extra positions) as text, with one line per row.
+    pub fn render<F>(&self, include: &[Point], mut cell: F) -> String
+    where
+        F: FnMut(Point, Option<&T>) -> char,
+    {
+        let bounds = BoundingBox::of(
+            self.bounds
+                .into_iter()
+                .flat_map(|bounds| [bounds.min, bounds.max])
+                .chain(include.iter().copied()),
+        );
+
+        let mut rendered = String::new();
+        for point in bounds.into_iter().flat_map(BoundingBox::points) {
+            rendered.push(cell(point, self.get(point)));
+            if Some(point.x) == bounds.map(|bounds| bounds.max.x) {
+                rendered.push('\n');
+            }
+        }
+        rendered
+    }
+}
+
+impl<T> FromIterator<(Point, T)> for SparseGrid<T> {
+    fn from_iter<I: IntoIterator<Item = (Point, T)>>(iter: I) -> Self {
+        let mut grid = SparseGrid::new();
+        for (position, value) in iter {
+            grid.insert(position, value);
+        }
+        grid
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{super::parsing::char_grid, *};
+
+    const EXAMPLE_GRID: &str = "123
+456
+";
+
+    fn example() -> Grid<u32> {
+        super::super::must_parse(char_grid(|_, c| c.to_digit(10)), EXAMPLE_GRID)
+    }
+
+    #[test]
+    fn test_parse_render() {
+        let grid = example();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[(2, 1)], 6);
+        assert_eq!(grid.get((3, 0)), None);
+        assert_eq!(
+            grid.render(|_, n| char::from_digit(*n, 10).unwrap()),
+            EXAMPLE_GRID
+        );
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let grid = example();
+        assert_eq!(
+            grid.neighbors4((0, 0)).collect::<Vec<_>>(),
+            vec![(0, 1), (1, 0)]
+        );
+        assert_eq!(grid.neighbors8((1, 0)).count(), 5);
+    }
+
+    #[test]
+    fn test_iterators() {
+        let grid = example();
+        assert_eq!(
+            grid.ray((2, 1), Direction::Left.vector())
+                .collect::<Vec<_>>(),
+            vec![(1, 1), (0, 1)]
+        );
+        assert_eq!(grid.ray((0, 0), Direction::Up.vector()).count(), 0);
+        assert_eq!(
+            grid.row(1).map(|p| grid[p]).collect::<Vec<_>>(),
+            vec![4, 5, 6]
+        );
+        assert_eq!(
+            grid.column(2).map(|p| grid[p]).collect::<Vec<_>>(),
+            vec![3, 6]
+        );
+        assert!(grid.is_edge((1, 0)));
+    }
+
+    #[test]
+    fn test_sparse() {
+        let grid: SparseGrid<char> = [(Point::new(-1, 0), '#'), (Point::new(1, 1), 'o')]
+            .into_iter()
+            .collect();
+        assert_eq!(
+            grid.bounds(),
+            BoundingBox::of([Point::new(-1, 0), Point::new(1, 1)])
+        );
+        assert_eq!(
+            grid.render(&[Point::new(0, -1)], |_, c| *c.unwrap_or(&'.')),
+            "...\n#..\n..o\n"
+        );
+    }
+}
@@ -0,0 +1,207 @@
+use std::{
+    io::{self, Write},
+    str::FromStr,
+};
+
+use super::grid::Grid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl Rgb {
+    pub const BLACK: Rgb = Rgb(0, 0, 0);
+    pub const WHITE: Rgb = Rgb(255, 255, 255);
+    pub const RED: Rgb = Rgb(220, 40, 40);
+
+    // Perceived brightness, using the Rec. 601 weights.
+    pub fn luma(self) -> u8 {
+        let Rgb(r, g, b) = self;
+        ((299 * r as u32 + 587 * g as u32 + 114 * b as u32) / 1000) as u8
+    }
+
+    // Scales each channel, where 0 is black and 1 leaves the color unchanged.
+    pub fn dim(self, brightness: f64) -> Rgb {
+        let scale = |channel: u8| (channel as f64 * brightness.clamp(0.0, 1.0)).round() as u8;
+        Rgb(scale(self.0), scale(self.1), scale(self.2))
+    }
+
+    fn lerp(self, other: Rgb, t: f64) -> Rgb {
+        let mix = |a: u8, b: u8| (a as f64 + (b as f64 - a as f64) * t).round() as u8;
+        Rgb(
+            mix(self.0, other.0),
+            mix(self.1, other.1),
+            mix(self.2, other.2),
+        )
+    }
+}
+
+// Maps values between 0 and 1 to colors, for drawing numeric grids like
+// heights and scores.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ColorMap {
+    Gray,
+    #[default]
+    Heat,
+    Terrain,
+}
+
+impl ColorMap {
+    pub const NAMES: [&'static str; 3] = ["gray", "heat", "terrain"];
+
+    // Evenly spaced colors, from 0 to 1.
+    fn stops(self) -> &'static [Rgb] {
+        match self {
+            ColorMap::Gray => &[Rgb::BLACK, Rgb::WHITE],
+            ColorMap::Heat => &[
+                Rgb::BLACK,
+                Rgb(128, 0, 32),
+                Rgb(230, 60, 0),
+                Rgb(255, 200, 0),
+                Rgb::WHITE,
+            ],
+            ColorMap::Terrain => &[
+                Rgb(20, 40, 120),
+                Rgb(40, 140, 60),
+                Rgb(200, 190, 90),
+                Rgb(120, 80, 50),
+                Rgb(245, 245, 245),
+            ],
+        }
+    }
+
+    pub fn color(self, t: f64) -> Rgb {
+        let stops = self.stops();
+        let position = t.clamp(0.0, 1.0) * (stops.len() - 1) as f64;
+        let i = (position.floor() as usize).min(stops.len() - 2);
+        stops[i].lerp(stops[i + 1], position - i as f64)
+    }
+
+    // Colors a value within an inclusive range of values.
+    pub fn scaled(self, value: f64, min: f64, max: f64) -> Rgb {
+        if max > min {
+            self.color((value - min) / (max - min))
+        } else {
+            self.color(1.0)
+        }
+    }
+}
+
+impl FromStr for ColorMap {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "gray" | "grey" => Ok(ColorMap::Gray),
+            "heat" => Ok(ColorMap::Heat),
+            "terrain" => Ok(ColorMap::Terrain),
+            _ => Err(format!(
+                "Unknown color map {:?} (expected one of: {})",
+                s,
+                ColorMap::NAMES.join(", ")
+            )),
+        }
+    }
+}
+
+// How a day should draw its image.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ImageOptions {
+    // Each output pixel covers a square of this many cells on a side.
+    pub downsample: usize,
+    pub color_map: ColorMap,
+}
+
+impl Default for ImageOptions {
+    fn default() -> Self {
+        ImageOptions {
+            downsample: 1,
+            color_map: ColorMap::default(),
+        }
+    }
+}
+
+pub type Image = Grid<Rgb>;
+
+impl Image {
+    // Averages each square of `factor` by `factor` pixels into one pixel.
+    // Squares at the right and bottom edges may be partial.
+    pub fn downsample(&self, factor: usize) -> Image {
+        if factor <= 1 {
+            return self.clone();
+        }
+        let (width, height) = (
+            self.width().div_ceil(factor),
+            self.height().div_ceil(factor),
+        );
+        let mut sums = vec![(0, 0, 0, 0); width * height];
+        for ((x, y), Rgb(r, g, b)) in self.iter() {
+            let sum = &mut sums[(y / factor) * width + x / factor];
+            *sum = (
+                sum.0 + *r as usize,
+                sum.1 + *g as usize,
+                sum.2 + *b as usize,
+                sum.3 + 1,
+            );
+        }
+        let pixels = sums
+            .into_iter()
+            .map(|(r, g, b, n)| Rgb((r / n) as u8, (g / n) as u8, (b / n) as u8))
+            .collect();
+        Grid::from_cells(width, pixels)
+    }
+
+    // Writes a binary PPM (P6) image.
+    pub fn write_ppm<W: Write>(&self, out: &mut W) -> io::Result<()> {
+        write!(out, "P6\n{} {}\n255\n", self.width(), self.height())?;
+        let bytes = self
+            .values()
+            .flat_map(|Rgb(r, g, b)| [*r, *g, *b])
+            .collect::<Vec<_>>();
+        out.write_all(&bytes)
+    }
+
+    // Writes a binary PGM (P5) image, converting colors to brightness.
+    pub fn write_pgm<W: Write>(&self, out: &mut W) -> io::Result<()> {
+        write!(out, "P5\n{} {}\n255\n", self.width(), self.height())?;
+        let bytes = self.values().map(|color| color.luma()).collect::<Vec<_>>();
+        out.write_all(&bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_color_maps() {
+        assert_eq!(ColorMap::Gray.color(0.0), Rgb::BLACK);
+        assert_eq!(ColorMap::Gray.color(0.5), Rgb(128, 128, 128));
+        assert_eq!(ColorMap::Heat.color(2.0), Rgb::WHITE);
+        assert_eq!(ColorMap::Terrain.scaled(3.0, 3.0, 3.0), Rgb(245, 245, 245));
+        assert_eq!("grey".parse(), Ok(ColorMap::Gray));
+        assert!("rainbow".parse::<ColorMap>().is_err());
+    }
+
+    #[test]
+    fn test_downsample() {
+        let mut image = Image::new(3, 2, Rgb::BLACK);
+        image[(0, 0)] = Rgb(200, 100, 0);
+        image[(2, 1)] = Rgb::WHITE;
+        let downsampled = image.downsample(2);
+        assert_eq!((downsampled.width(), downsampled.height()), (2, 1));
+        assert_eq!(downsampled[(0, 0)], Rgb(50, 25, 0));
+        assert_eq!(downsampled[(1, 0)], Rgb(127, 127, 127));
+    }
+
+    #[test]
+    fn test_write() {
+        let mut image = Image::new(2, 1, Rgb::WHITE);
+        image[(1, 0)] = Rgb(255, 0, 0);
+        let mut ppm = Vec::new();
+        image.write_ppm(&mut ppm).unwrap();
+        assert_eq!(ppm, b"P6\n2 1\n255\n\xff\xff\xff\xff\x00\x00");
+        let mut pgm = Vec::new();
+        image.write_pgm(&mut pgm).unwrap();
+        assert_eq!(pgm, b"P5\n2 1\n255\n\xff\x4c");
+    }
+}
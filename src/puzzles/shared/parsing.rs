@@ -0,0 +1,425 @@
+use std::{
+    fmt::{self, Display, Formatter},
+    str::FromStr,
+};
+
+use nom::{
+    bytes::complete::tag,
+    character::complete::{char, digit1, newline, one_of},
+    combinator::{cut, map, map_res, opt, recognize},
+    error::{ContextError, ErrorKind, FromExternalError},
+    multi::{count, many0},
+    sequence::{delimited, pair, preceded, terminated},
+    Parser,
+};
+
+use super::{
+    fail,
+    grid::{Grid, Position},
+};
+
+// Puzzle parsers use this instead of nom's IResult, so that failures can be
+// reported with the line and column where parsing went wrong.
+pub type IResult<'a, T> = nom::IResult<&'a str, T, ParseError<'a>>;
+
+// A parse failure, holding the remaining input at the point of failure.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError<'a> {
+    pub input: &'a str,
+    pub expected: Expected,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Expected {
+    Kind(ErrorKind),
+    Char(char),
+    Context(&'static str),
+}
+
+impl<'a> ParseError<'a> {
+    pub fn context(input: &'a str, context: &'static str) -> ParseError<'a> {
+        ParseError {
+            input,
+            expected: Expected::Context(context),
+        }
+    }
+}
+
+impl<'a> nom::error::ParseError<&'a str> for ParseError<'a> {
+    fn from_error_kind(input: &'a str, kind: ErrorKind) -> Self {
+        ParseError {
+            input,
+            expected: Expected::Kind(kind),
+        }
+    }
+
+    // Keep the innermost error, since it's the most precise.
+    fn append(_: &'a str, _: ErrorKind, other: Self) -> Self {
+        other
+    }
+
+    fn from_char(input: &'a str, c: char) -> Self {
+        ParseError {
+            input,
+            expected: Expected::Char(c),
+        }
+    }
+
+    // When every alternative fails, report the one that got furthest.
+    fn or(self, other: Self) -> Self {
+        if other.input.len() <= self.input.len() {
+            other
+        } else {
+            self
+        }
+    }
+}
+
+impl<'a> ContextError<&'a str> for ParseError<'a> {
+    // Only replace nom's error kinds, so that the innermost context wins.
+    fn add_context(_: &'a str, context: &'static str, other: Self) -> Self {
+        match other.expected {
+            Expected::Kind(_) => ParseError {
+                expected: Expected::Context(context),
+                ..other
+            },
+            _ => other,
+        }
+    }
+}
+
+impl<'a, E> FromExternalError<&'a str, E> for ParseError<'a> {
+    fn from_external_error(input: &'a str, kind: ErrorKind, _: E) -> Self {
+        nom::error::ParseError::from_error_kind(input, kind)
+    }
+}
+
+impl Display for Expected {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Expected::Kind(kind) => write!(f, "{}", kind.description().to_lowercase()),
+            Expected::Char(c) => write!(f, "{:?}", c),
+            Expected::Context(context) => write!(f, "{}", context),
+        }
+    }
+}
+
+// A 1-indexed line and column within the original input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Location {
+    pub line: usize,
+    pub column: usize,
+}
+
+impl Location {
+    // Finds where the remaining input starts within the original input.
+    pub fn of(source: &str, remaining: &str) -> Location {
+        let consumed = &source[..source.len() - remaining.len()];
+        let line_start = consumed.rfind('\n').map_or(0, |i| i + 1);
+        Location {
+            line: consumed.matches('\n').count() + 1,
+            column: consumed[line_start..].chars().count() + 1,
+        }
+    }
+}
+
+impl Display for Location {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}, column {}", self.line, self.column)
+    }
+}
+
+// Describes what was found at the start of the remaining input: the rest of
+// the current line, or the end of the input.
+fn found(remaining: &str) -> String {
+    match remaining.lines().next() {
+        None => "end of input".into(),
+        Some("") => "end of line".into(),
+        Some(line) => format!("{:?}", line),
+    }
+}
+
+// Runs a parser that must consume the whole input.
+pub fn parse_complete<'a, F, T>(mut parser: F, input: &'a str) -> Result<T, String>
+where
+    F: FnMut(&'a str) -> IResult<'a, T>,
+{
+    match parser(input) {
+        Ok(("", parsed)) => Ok(parsed),
+        Ok((remaining, _)) => Err(format!(
+            "{}: unexpected {}",
+            Location::of(input, remaining),
+            found(remaining)
+        )),
+        Err(nom::Err::Error(err) | nom::Err::Failure(err)) => Err(format!(
+            "{}: expected {}, found {}",
+            Location::of(input, err.input),
+            err.expected,
+            found(err.input)
+        )),
+        Err(nom::Err::Incomplete(_)) => Err("incomplete input".into()),
+    }
+}
+
+pub fn must_parse<'a, F, T>(parser: F, input: &'a str) -> T
+where
+    F: FnMut(&'a str) -> IResult<'a, T>,
+{
+    parse_complete(parser, input)
+        .unwrap_or_else(|err| fail!("Could not parse puzzle input: {}", err))
+}
+
+// One or more lines, each parsed by `f` and terminated by a newline. Stops
+// at the end of the input or at a blank line. Unlike `many1`, a line that
+// fails to parse is an error rather than the end of the list, so the error
+// points at the bad line.
+pub fn lines<'a, T, F>(mut f: F) -> impl FnMut(&'a str) -> IResult<'a, Vec<T>>
+where
+    F: Parser<&'a str, T, ParseError<'a>>,
+{
+    move |mut input: &'a str| {
+        let mut parsed = Vec::new();
+        loop {
+            let (rest, line) = terminated(|i| f.parse(i), newline)(input)?;
+            parsed.push(line);
+            input = rest;
+            if input.is_empty() || input.starts_with('\n') {
+                return Ok((input, parsed));
+            }
+        }
+    }
+}
+
+// One or more blocks separated by blank lines. Each block is parsed by `f`,
+// which should consume the block's lines including their newlines (for
+// example, with `lines`).
+pub fn blocks<'a, T, F>(mut f: F) -> impl FnMut(&'a str) -> IResult<'a, Vec<T>>
+where
+    F: Parser<&'a str, T, ParseError<'a>>,
+{
+    move |input: &'a str| {
+        let (mut input, first) = f.parse(input)?;
+        let mut parsed = vec![first];
+        while let Some(rest) = input.strip_prefix('\n') {
+            let (rest, block) = f.parse(rest)?;
+            parsed.push(block);
+            input = rest;
+        }
+        Ok((input, parsed))
+    }
+}
+
+// Exactly `n` items separated by `sep`.
+pub fn separated_listn<'a, T, S, F, G>(
+    sep: G,
+    f: F,
+    n: usize,
+) -> impl FnMut(&'a str) -> IResult<'a, Vec<T>>
+where
+    F: Parser<&'a str, T, ParseError<'a>> + Clone,
+    G: Parser<&'a str, S, ParseError<'a>>,
+{
+    let mut rest = count(preceded(sep, f.clone()), n.saturating_sub(1));
+    let mut first = f;
+    move |input: &'a str| {
+        if n == 0 {
+            return Ok((input, Vec::new()));
+        }
+        let (input, head) = first.parse(input)?;
+        let (input, mut tail) = rest(input)?;
+        tail.insert(0, head);
+        Ok((input, tail))
+    }
+}
+
+pub fn unsigned<'a, T: FromStr>(input: &'a str) -> IResult<'a, T> {
+    nom::error::context("unsigned integer", map_res(digit1, str::parse))(input)
+}
+
+pub fn signed<'a, T: FromStr>(input: &'a str) -> IResult<'a, T> {
+    nom::error::context(
+        "integer",
+        map_res(recognize(pair(opt(one_of("+-")), digit1)), str::parse),
+    )(input)
+}
+
+// One or more unsigned integers separated by `sep`, like "79, 98".
+pub fn unsigned_list<'a, T: FromStr>(
+    sep: &'static str,
+) -> impl FnMut(&'a str) -> IResult<'a, Vec<T>> {
+    separated_integers(sep, unsigned)
+}
+
+// One or more signed integers separated by `sep`, like "1,-2,3".
+pub fn signed_list<'a, T: FromStr>(
+    sep: &'static str,
+) -> impl FnMut(&'a str) -> IResult<'a, Vec<T>> {
+    separated_integers(sep, signed)
+}
+
+// Once a separator has been read, an integer must follow it, so that a bad
+// integer is reported where it is rather than ending the list early.
+fn separated_integers<'a, T>(
+    sep: &'static str,
+    integer: fn(&'a str) -> IResult<'a, T>,
+) -> impl FnMut(&'a str) -> IResult<'a, Vec<T>> {
+    map(
+        pair(integer, many0(preceded(tag(sep), cut(integer)))),
+        |(first, mut rest)| {
+            rest.insert(0, first);
+            rest
+        },
+    )
+}
+
+// A `key=value` assignment, like "x=-2".
+pub fn key_value<'a, T, F>(key: &'static str, value: F) -> impl FnMut(&'a str) -> IResult<'a, T>
+where
+    F: Parser<&'a str, T, ParseError<'a>>,
+{
+    preceded(pair(tag(key), char('=')), value)
+}
+
+// A whole line made of a fixed label followed by a value, like
+// "  Test: divisible by 23".
+pub fn field<'a, T, F>(label: &'static str, value: F) -> impl FnMut(&'a str) -> IResult<'a, T>
+where
+    F: Parser<&'a str, T, ParseError<'a>>,
+{
+    delimited(tag(label), value, newline)
+}
+
+// A rectangular grid of characters, one newline-terminated row per line,
+// ending at the end of the input or at a blank line. Each character is mapped
+// to a cell, and characters mapped to None are reported as errors.
+pub fn char_grid<'a, T, F>(mut cell: F) -> impl FnMut(&'a str) -> IResult<'a, Grid<T>>
+where
+    F: FnMut(Position, char) -> Option<T>,
+{
+    move |input: &'a str| {
+        let mut cells = Vec::new();
+        let mut width = None;
+        let mut rest = input;
+        let mut y = 0;
+        while !rest.is_empty() && !rest.starts_with('\n') {
+            let (row, after) = rest.split_once('\n').ok_or_else(|| {
+                nom::Err::Error(ParseError::context(&rest[rest.len()..], "newline"))
+            })?;
+            let mut x = 0;
+            for (offset, c) in row.char_indices() {
+                if width.is_some_and(|width| x >= width) {
+                    return Err(nom::Err::Error(ParseError::context(
+                        &rest[offset..],
+                        "end of row (rows must have equal widths)",
+                    )));
+                }
+                match cell((x, y), c) {
+                    Some(value) => cells.push(value),
+                    None => {
+                        return Err(nom::Err::Error(ParseError::context(
+                            &rest[offset..],
+                            "grid cell",
+                        )))
+                    }
+                }
+                x += 1;
+            }
+            match width {
+                None => width = Some(x),
+                Some(width) if x < width => {
+                    return Err(nom::Err::Error(ParseError::context(
+                        &rest[row.len()..],
+                        "more cells (rows must have equal widths)",
+                    )))
+                }
+                Some(_) => {}
+            }
+            rest = after;
+            y += 1;
+        }
+        match width {
+            Some(width) if width > 0 => Ok((rest, Grid::from_cells(width, cells))),
+            _ => Err(nom::Err::Error(ParseError::context(rest, "grid row"))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use nom::character::complete::u32;
+
+    use super::*;
+
+    #[test]
+    fn test_lines_and_blocks() {
+        assert_eq!(
+            parse_complete(blocks(lines(unsigned::<u32>)), "1\n2\n\n3\n"),
+            Ok(vec![vec![1, 2], vec![3]])
+        );
+        assert_eq!(
+            parse_complete(blocks(lines(unsigned::<u32>)), "1\n2\n\nx\n"),
+            Err("line 4, column 1: expected unsigned integer, found \"x\"".into())
+        );
+        assert_eq!(
+            parse_complete(lines(u32), "1\n2"),
+            Err("line 2, column 2: expected '\\n', found end of input".into())
+        );
+    }
+
+    #[test]
+    fn test_separated_listn() {
+        let mut three = separated_listn(char(','), unsigned::<u8>, 3);
+        assert_eq!(three("1,2,3,4"), Ok((",4", vec![1, 2, 3])));
+        assert_eq!(
+            parse_complete(three, "1,2"),
+            Err("line 1, column 4: expected ',', found end of input".into())
+        );
+    }
+
+    #[test]
+    fn test_integers() {
+        assert_eq!(
+            parse_complete(signed_list::<i64>(", "), "-1, 2, +3"),
+            Ok(vec![-1, 2, 3])
+        );
+        assert_eq!(
+            parse_complete(signed_list::<i8>(","), "1,-200"),
+            Err("line 1, column 3: expected integer, found \"-200\"".into())
+        );
+        assert_eq!(
+            parse_complete(unsigned_list::<u8>(","), "1,300"),
+            Err("line 1, column 3: expected unsigned integer, found \"300\"".into())
+        );
+    }
+
+    #[test]
+    fn test_key_value_and_field() {
+        let mut sensor = pair(
+            key_value("x", signed::<i32>),
+            preceded(tag(", "), key_value("y", signed::<i32>)),
+        );
+        assert_eq!(sensor("x=-2, y=15"), Ok(("", (-2, 15))));
+        assert_eq!(
+            parse_complete(
+                field("Test: divisible by ", unsigned::<u32>),
+                "Test: divisible by 23\n"
+            ),
+            Ok(23)
+        );
+    }
+
+    #[test]
+    fn test_char_grid() {
+        let digits = |_, c: char| c.to_digit(10);
+        let grid = parse_complete(char_grid(digits), "12\n34\n").unwrap();
+        assert_eq!(grid[(1, 1)], 4);
+        assert_eq!(
+            parse_complete(char_grid(digits), "12\n3x\n"),
+            Err("line 2, column 2: expected grid cell, found \"x\"".into())
+        );
+        assert_eq!(
+            parse_complete(char_grid(digits), "12\n3\n"),
+            Err("line 2, column 2: expected more cells (rows must have equal widths), found end of line".into())
+        );
+    }
+}
@@ -0,0 +1,264 @@
+use std::{
+    fmt::{self, Debug, Formatter},
+    ops::RangeInclusive,
+};
+
+// The integer types a RangeSet can hold. Stepping is checked, so that ranges
+// can reach the ends of the type without overflowing.
+pub trait Integer: Copy + Ord + Debug {
+    fn successor(self) -> Option<Self>;
+    fn predecessor(self) -> Option<Self>;
+    // Wide enough to hold the length of any range of this type.
+    fn to_i128(self) -> i128;
+}
+
+macro_rules! impl_integer {
+    ($($t:ty),*) => {
+        $(
+            impl Integer for $t {
+                fn successor(self) -> Option<Self> {
+                    self.checked_add(1)
+                }
+
+                fn predecessor(self) -> Option<Self> {
+                    self.checked_sub(1)
+                }
+
+                fn to_i128(self) -> i128 {
+                    self as i128
+                }
+            }
+        )*
+    };
+}
+
+impl_integer!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+
+// A set of integers, stored as sorted inclusive intervals. Overlapping and
+// adjacent intervals are merged, so two sets with the same members always
+// have the same intervals.
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct RangeSet<T> {
+    ranges: Vec<(T, T)>,
+}
+
+impl<T: Integer> Default for RangeSet<T> {
+    fn default() -> Self {
+        RangeSet { ranges: Vec::new() }
+    }
+}
+
+impl<T: Integer> RangeSet<T> {
+    pub fn new() -> RangeSet<T> {
+        RangeSet::default()
+    }
+
+    // Adds every integer in the range. Empty ranges are ignored.
+    pub fn insert(&mut self, range: RangeInclusive<T>) {
+        let (start, end) = range.into_inner();
+        if start > end {
+            return;
+        }
+        // Find the existing ranges that overlap or touch the new one.
+        let first = self
+            .ranges
+            .partition_point(|(_, e)| e.successor().is_some_and(|after| after < start));
+        let last = self
+            .ranges
+            .partition_point(|(s, _)| s.predecessor().is_none_or(|before| before <= end));
+        let merged = if first < last {
+            (
+                start.min(self.ranges[first].0),
+                end.max(self.ranges[last - 1].1),
+            )
+        } else {
+            (start, end)
+        };
+        self.ranges.splice(first..last, [merged]);
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        let i = self.ranges.partition_point(|(_, e)| *e < value);
+        self.ranges.get(i).is_some_and(|(s, _)| *s <= value)
+    }
+
+    pub fn is_superset(&self, other: &RangeSet<T>) -> bool {
+        other.difference(self).is_empty()
+    }
+
+    // The number of integers in the set.
+    pub fn len(&self) -> u128 {
+        self.ranges
+            .iter()
+            .map(|(s, e)| (e.to_i128() - s.to_i128()) as u128 + 1)
+            .sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    pub fn min(&self) -> Option<T> {
+        self.ranges.first().map(|(s, _)| *s)
+    }
+
+    pub fn max(&self) -> Option<T> {
+        self.ranges.last().map(|(_, e)| *e)
+    }
+
+    // The disjoint ranges making up the set, in ascending order.
+    pub fn iter(&self) -> impl Iterator<Item = RangeInclusive<T>> + '_ {
+        self.ranges.iter().map(|(s, e)| *s..=*e)
+    }
+
+    pub fn union(&self, other: &RangeSet<T>) -> RangeSet<T> {
+        let mut union = self.clone();
+        for range in other.iter() {
+            union.insert(range);
+        }
+        union
+    }
+
+    pub fn intersection(&self, other: &RangeSet<T>) -> RangeSet<T> {
+        let mut ranges = Vec::new();
+        let (mut i, mut j) = (0, 0);
+        while let (Some((a_start, a_end)), Some((b_start, b_end))) =
+            (self.ranges.get(i), other.ranges.get(j))
+        {
+            let (start, end) = (*a_start.max(b_start), *a_end.min(b_end));
+            if start <= end {
+                ranges.push((start, end));
+            }
+            // Move past whichever range ends first.
+            if a_end < b_end {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+        RangeSet { ranges }
+    }
+
+    pub fn difference(&self, other: &RangeSet<T>) -> RangeSet<T> {
+        let mut ranges = Vec::new();
+        for (start, end) in self.ranges.iter().copied() {
+            let mut remaining = Some(start);
+            let first = other.ranges.partition_point(|(_, e)| *e < start);
+            for (cut_start, cut_end) in other.ranges[first..].iter().copied() {
+                let Some(from) = remaining.filter(|from| *from <= end) else {
+                    break;
+                };
+                if cut_start > end {
+                    break;
+                }
+                if cut_start > from {
+                    // Can't underflow, since cut_start > from.
+                    ranges.push((from, cut_start.predecessor().unwrap_or(from)));
+                }
+                remaining = cut_end.successor().map(|after| after.max(from));
+            }
+            if let Some(from) = remaining.filter(|from| *from <= end) {
+                ranges.push((from, end));
+            }
+        }
+        RangeSet { ranges }
+    }
+
+    // The integers between the set's minimum and maximum that aren't in it.
+    pub fn gaps(&self) -> RangeSet<T> {
+        RangeSet {
+            ranges: self
+                .ranges
+                .windows(2)
+                .filter_map(|pair| match pair {
+                    // Ranges are never adjacent, so neither step can overflow.
+                    [(_, before), (after, _)] => Some((before.successor()?, after.predecessor()?)),
+                    _ => None,
+                })
+                .collect(),
+        }
+    }
+}
+
+impl<T: Integer> From<RangeInclusive<T>> for RangeSet<T> {
+    fn from(range: RangeInclusive<T>) -> Self {
+        let mut set = RangeSet::new();
+        set.insert(range);
+        set
+    }
+}
+
+impl<T: Integer> FromIterator<RangeInclusive<T>> for RangeSet<T> {
+    fn from_iter<I: IntoIterator<Item = RangeInclusive<T>>>(iter: I) -> Self {
+        let mut set = RangeSet::new();
+        for range in iter {
+            set.insert(range);
+        }
+        set
+    }
+}
+
+impl<T: Integer> Debug for RangeSet<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn set(ranges: &[RangeInclusive<i32>]) -> RangeSet<i32> {
+        ranges.iter().cloned().collect()
+    }
+
+    #[test]
+    fn test_insert_merges() {
+        let mut ranges = set(&[1..=3, 7..=9]);
+        ranges.insert(4..=5);
+        assert_eq!(ranges, set(&[1..=5, 7..=9]));
+        ranges.insert(6..=6);
+        assert_eq!(ranges, set(&[1..=9]));
+        ranges.insert(-5..=-3);
+        let (start, end) = (3, 2);
+        ranges.insert(start..=end);
+        assert_eq!(format!("{:?}", ranges), "{-5..=-3, 1..=9}");
+        assert_eq!(ranges.len(), 12);
+    }
+
+    #[test]
+    fn test_contains() {
+        let ranges = set(&[1..=3, 7..=9]);
+        assert!(ranges.contains(1) && ranges.contains(8));
+        assert!(!ranges.contains(0) && !ranges.contains(5) && !ranges.contains(10));
+        assert!(ranges.is_superset(&set(&[2..=3, 9..=9])));
+        assert!(!ranges.is_superset(&set(&[3..=7])));
+    }
+
+    #[test]
+    fn test_set_operations() {
+        let a = set(&[1..=5, 10..=15]);
+        let b = set(&[4..=11, 15..=20]);
+        assert_eq!(a.union(&b), set(&[1..=20]));
+        assert_eq!(a.intersection(&b), set(&[4..=5, 10..=11, 15..=15]));
+        assert_eq!(a.difference(&b), set(&[1..=3, 12..=14]));
+        assert_eq!(b.difference(&a), set(&[6..=9, 16..=20]));
+        assert_eq!(a.gaps(), set(&[6..=9]));
+    }
+
+    #[test]
+    fn test_extremes() {
+        let mut ranges: RangeSet<u8> = (250..=255).into();
+        ranges.insert(0..=2);
+        ranges.insert(3..=249);
+        assert_eq!(ranges.len(), 256);
+        assert_eq!(
+            RangeSet::from(0..=255u8).difference(&(0..=255).into()),
+            RangeSet::new()
+        );
+        assert_eq!(
+            RangeSet::from(0..=255u8).difference(&(1..=254).into()),
+            [0..=0, 255..=255].into_iter().collect()
+        );
+    }
+}
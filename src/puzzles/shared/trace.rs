@@ -0,0 +1,169 @@
+use std::{
+    fmt::Display,
+    io::{self, Write},
+    str::FromStr,
+    sync::{
+        atomic::{AtomicU8, Ordering},
+        Mutex, PoisonError,
+    },
+};
+
+// How much detail a trace event is worth. Tracing at a level also shows the
+// events of every level above it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    // A few events per puzzle part, like each round or move.
+    Info = 1,
+    // Every step of a simulation.
+    Debug = 2,
+    // Everything, including the state after each step.
+    Trace = 3,
+}
+
+impl Level {
+    // -v is Info, -vv is Debug, and -vvv (or more) is Trace.
+    pub fn from_verbosity(count: u8) -> Option<Level> {
+        match count {
+            0 => None,
+            1 => Some(Level::Info),
+            2 => Some(Level::Debug),
+            _ => Some(Level::Trace),
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Level::Info => "info",
+            Level::Debug => "debug",
+            Level::Trace => "trace",
+        }
+    }
+}
+
+impl FromStr for Level {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "info" => Ok(Level::Info),
+            "debug" => Ok(Level::Debug),
+            "trace" => Ok(Level::Trace),
+            _ => Err(format!(
+                "Unknown trace level {:?} (expected info, debug or trace)",
+                s
+            )),
+        }
+    }
+}
+
+// Zero when tracing is off.
+static LEVEL: AtomicU8 = AtomicU8::new(0);
+// Where events go. Events go to stderr if there's no sink.
+type Sink = Box<dyn Write + Send>;
+static SINK: Mutex<Option<Sink>> = Mutex::new(None);
+
+// Starts (or stops) tracing events at the given level and above.
+pub fn enable(level: Option<Level>, sink: Option<Sink>) {
+    *SINK.lock().unwrap_or_else(PoisonError::into_inner) = sink;
+    LEVEL.store(level.map_or(0, |level| level as u8), Ordering::Relaxed);
+}
+
+pub fn enabled(level: Level) -> bool {
+    LEVEL.load(Ordering::Relaxed) >= level as u8
+}
+
+// Writes an event. Use the trace! macro rather than calling this directly, so
+// that events cost nothing when tracing is disabled.
+pub fn emit(level: Level, module: &str, event: &str, fields: &[(&str, &dyn Display)]) {
+    let line = format_event(level, module, event, fields);
+    let mut sink = SINK.lock().unwrap_or_else(PoisonError::into_inner);
+    // Tracing is best effort, and shouldn't fail the solver.
+    let _ = match sink.as_mut() {
+        Some(sink) => writeln!(sink, "{}", line).and_then(|()| sink.flush()),
+        None => writeln!(io::stderr(), "{}", line),
+    };
+}
+
+// Formats an event as a line like `[2022 day5 debug] move quantity=3 from=1 to=2`.
+fn format_event(
+    level: Level,
+    module: &str,
+    event: &str,
+    fields: &[(&str, &dyn Display)],
+) -> String {
+    let mut line = format!("[{} {}] {}", source(module), level.name(), event);
+    for (key, value) in fields {
+        line.push_str(&format!(" {}={}", key, value));
+    }
+    line
+}
+
+// Names the puzzle that an event came from, given the module that emitted it.
+pub(super) fn source(module: &str) -> String {
+    puzzle(module).unwrap_or_else(|| module.rsplit("::").next().unwrap_or(module).to_string())
+}
+
+// Names the puzzle a module (or a source file, like
+// `src/puzzles/year2022/day5.rs`) belongs to, like `2022 day5`, if it belongs
+// to one.
+pub(super) fn puzzle(module: &str) -> Option<String> {
+    let segments: Vec<&str> = module
+        .split(['/', '\\', ':'])
+        .map(|segment| segment.trim_end_matches(".rs"))
+        .collect();
+    let year = segments
+        .iter()
+        .find_map(|segment| segment.strip_prefix("year"))?;
+    let day = segments.iter().find(|segment| segment.starts_with("day"))?;
+    Some(format!("{} {}", year, day))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_event() {
+        assert_eq!(
+            format_event(
+                Level::Debug,
+                "advent2022::puzzles::year2022::day5",
+                "move",
+                &[("quantity", &3), ("from", &1), ("to", &"2")]
+            ),
+            "[2022 day5 debug] move quantity=3 from=1 to=2"
+        );
+        assert_eq!(
+            format_event(
+                Level::Info,
+                "advent2022::puzzles::shared::grid",
+                "done",
+                &[]
+            ),
+            "[grid info] done"
+        );
+    }
+
+    #[test]
+    fn test_puzzle() {
+        assert_eq!(
+            puzzle("advent2022::puzzles::year2022::day5").as_deref(),
+            Some("2022 day5")
+        );
+        assert_eq!(
+            puzzle("src/puzzles/year2022/day11.rs").as_deref(),
+            Some("2022 day11")
+        );
+        assert_eq!(puzzle("src/puzzles/shared/grid.rs"), None);
+    }
+
+    #[test]
+    fn test_levels() {
+        assert_eq!(Level::from_verbosity(0), None);
+        assert_eq!(Level::from_verbosity(2), Some(Level::Debug));
+        assert_eq!(Level::from_verbosity(9), Some(Level::Trace));
+        assert_eq!("info".parse(), Ok(Level::Info));
+        assert!("loud".parse::<Level>().is_err());
+        assert!(Level::Trace > Level::Info);
+    }
+}
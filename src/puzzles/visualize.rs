@@ -0,0 +1,21 @@
+use super::params::Params;
+
+// A simulation that can be watched one step at a time. Visualizers parse
+// their input when constructed.
+pub trait Visualize {
+    // Advances the simulation by one step. Returns false, without changing
+    // anything, once the simulation has finished.
+    fn step(&mut self) -> bool;
+
+    // Renders the current state of the simulation as text.
+    fn render(&self) -> String;
+}
+
+// Visualizers take the same parameters as the part they visualize, so that
+// they show the same run.
+pub type Visualizer = fn(String, &Params) -> Box<dyn Visualize>;
+
+// Returns the visualizer for puzzle parts that have one.
+pub fn visualizer(year: u32, day: u32, part: u32) -> Option<Visualizer> {
+    (super::find_year(year)?.visualizer)(day, part)
+}
@@ -0,0 +1,221 @@
+pub mod day1;
+pub mod day2;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+pub mod day7;
+pub mod day8;
+pub mod day9;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+pub mod day14;
+pub mod day15;
+
+// Days refer to the modules shared by every year through these, as
+// `super::shared` and so on.
+use super::{
+    explore::{self, Explorer},
+    image::Painter,
+    inspect::{self, Inspector},
+    params,
+    report::Reporter,
+    shared,
+    visualize::{self, Visualizer},
+    Day,
+};
+
+pub static DAYS: &[Day] = &[
+    Day {
+        number: 1,
+        parse: Some(|input| drop(shared::must_parse(day1::parse, input))),
+        part1: |input, _| day1::part1(input).to_string(),
+        part2: |input, params| day1::part2(input, params).to_string(),
+    },
+    Day {
+        number: 2,
+        parse: Some(|input| drop(shared::must_parse(day2::parse, input))),
+        part1: |input, params| day2::part1(input, params).to_string(),
+        part2: |input, params| day2::part2(input, params).to_string(),
+    },
+    Day {
+        number: 3,
+        parse: Some(|input| drop(day3::parse(input))),
+        part1: |input, _| day3::part1(input).to_string(),
+        part2: |input, params| day3::part2(input, params).to_string(),
+    },
+    Day {
+        number: 4,
+        parse: Some(|input| drop(shared::must_parse(day4::parse, input))),
+        part1: |input, _| day4::part1(input).to_string(),
+        part2: |input, _| day4::part2(input).to_string(),
+    },
+    Day {
+        number: 5,
+        parse: Some(|input| drop(shared::must_parse(day5::parse_puzzle, input))),
+        part1: |input, params| day5::part1(input, params),
+        part2: |input, params| day5::part2(input, params),
+    },
+    Day {
+        number: 6,
+        parse: None,
+        part1: |input, _| day6::part1(input).to_string(),
+        part2: |input, _| day6::part2(input).to_string(),
+    },
+    Day {
+        number: 7,
+        parse: Some(|input| drop(day7::parse_filesystem(input))),
+        part1: |input, _| day7::part1(input).to_string(),
+        part2: |input, _| day7::part2(input).to_string(),
+    },
+    Day {
+        number: 8,
+        parse: Some(|input| drop(day8::parse_rectangular_grid(input))),
+        part1: |input, _| day8::part1(input).to_string(),
+        part2: |input, _| day8::part2(input).to_string(),
+    },
+    Day {
+        number: 9,
+        parse: Some(|input| drop(shared::must_parse(day9::parse, input))),
+        part1: |input, _| day9::part1(input).to_string(),
+        part2: |input, _| day9::part2(input).to_string(),
+    },
+    Day {
+        number: 10,
+        parse: Some(|input| drop(shared::must_parse(day10::parse, input))),
+        part1: |input, _| day10::part1(input).to_string(),
+        part2: |input, _| day10::part2(input),
+    },
+    Day {
+        number: 11,
+        parse: Some(|input| drop(shared::must_parse(day11::parse, input))),
+        part1: |input, _| day11::part1(input).to_string(),
+        part2: |input, _| day11::part2(input).to_string(),
+    },
+    Day {
+        number: 12,
+        parse: Some(|input| drop(day12::parse(input))),
+        part1: |input, _| day12::part1(input).to_string(),
+        part2: |input, _| day12::part2(input).to_string(),
+    },
+    Day {
+        number: 13,
+        parse: Some(|input| drop(shared::must_parse(day13::parse, input))),
+        part1: |input, _| day13::part1(input).to_string(),
+        part2: |input, _| day13::part2(input).to_string(),
+    },
+    Day {
+        number: 14,
+        parse: Some(|input| drop(shared::must_parse(day14::parse, input))),
+        part1: |input, _| day14::part1(input).to_string(),
+        part2: |input, _| day14::part2(input).to_string(),
+    },
+    Day {
+        number: 15,
+        parse: Some(|input| drop(shared::must_parse(day15::parse, input))),
+        part1: |input, params| day15::part1(input, params).to_string(),
+        part2: |input, params| day15::part2(input, params).to_string(),
+    },
+];
+
+pub fn explorer(day: u32) -> Option<Explorer> {
+    match day {
+        7 => Some(|input| Box::new(day7::Explorer::new(input))),
+        12 => Some(|input| Box::new(day12::Explorer::new(input))),
+        13 => Some(|input| Box::new(day13::Explorer::new(input))),
+        _ => None,
+    }
+}
+
+pub fn inspector(day: u32) -> Option<Inspector> {
+    match day {
+        4 => Some(Inspector {
+            summarize: day4::summarize,
+            dump: day4::dump,
+        }),
+        5 => Some(Inspector {
+            summarize: day5::summarize,
+            dump: day5::dump,
+        }),
+        7 => Some(Inspector {
+            summarize: day7::summarize,
+            dump: day7::dump,
+        }),
+        8 => Some(Inspector {
+            summarize: day8::summarize,
+            dump: day8::dump,
+        }),
+        9 => Some(Inspector {
+            summarize: day9::summarize,
+            dump: day9::dump,
+        }),
+        10 => Some(Inspector {
+            summarize: day10::summarize,
+            dump: day10::dump,
+        }),
+        11 => Some(Inspector {
+            summarize: day11::summarize,
+            dump: day11::dump,
+        }),
+        12 => Some(Inspector {
+            summarize: day12::summarize,
+            dump: day12::dump,
+        }),
+        13 => Some(Inspector {
+            summarize: day13::summarize,
+            dump: day13::dump,
+        }),
+        14 => Some(Inspector {
+            summarize: day14::summarize,
+            dump: day14::dump,
+        }),
+        15 => Some(Inspector {
+            summarize: day15::summarize,
+            dump: day15::dump,
+        }),
+        _ => None,
+    }
+}
+
+pub fn reporter(day: u32) -> Option<Reporter> {
+    match day {
+        1 => Some(day1::report),
+        2 => Some(day2::report),
+        3 => Some(day3::report),
+        4 => Some(day4::report),
+        5 => Some(day5::report),
+        _ => None,
+    }
+}
+
+pub fn visualizer(day: u32, part: u32) -> Option<Visualizer> {
+    match (day, part) {
+        (5, 1) => Some(|input, params| Box::new(day5::part1_visualizer(input, params))),
+        (5, 2) => Some(|input, params| Box::new(day5::part2_visualizer(input, params))),
+        (9, 1) => Some(|input, _| Box::new(day9::part1_visualizer(input))),
+        (9, 2) => Some(|input, _| Box::new(day9::part2_visualizer(input))),
+        (10, 1) => Some(|input, _| Box::new(day10::part1_visualizer(input))),
+        (10, 2) => Some(|input, _| Box::new(day10::part2_visualizer(input))),
+        (11, 1) => Some(|input, _| Box::new(day11::part1_visualizer(input))),
+        (11, 2) => Some(|input, _| Box::new(day11::part2_visualizer(input))),
+        (14, 1) => Some(|input, _| Box::new(day14::part1_visualizer(input))),
+        (14, 2) => Some(|input, _| Box::new(day14::part2_visualizer(input))),
+        _ => None,
+    }
+}
+
+pub fn painter(day: u32, part: u32) -> Option<Painter> {
+    match (day, part) {
+        (8, 1) => Some(day8::part1_image),
+        (8, 2) => Some(day8::part2_image),
+        (10, 1 | 2) => Some(day10::image),
+        (12, 1) => Some(day12::part1_image),
+        (12, 2) => Some(day12::part2_image),
+        (14, 1) => Some(day14::part1_image),
+        (14, 2) => Some(day14::part2_image),
+        (15, 1 | 2) => Some(day15::image),
+        _ => None,
+    }
+}
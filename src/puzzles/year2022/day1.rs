@@ -0,0 +1,241 @@
+use std::cmp::Reverse;
+
+use itertools::Itertools;
+use nom::combinator::map;
+
+use super::{
+    inspect::Summary,
+    params::Params,
+    shared::{
+        checked, must_parse,
+        parsing::{blocks, lines, unsigned, IResult},
+    },
+};
+
+// Both parts ask for the calories carried by the elves carrying the most: the
+// top elf for part 1, and the top three for part 2.
+pub fn part1(input: String) -> u64 {
+    top_calories(input, 1)
+}
+
+// Part 2's k is a parameter, for asking about more (or fewer) elves.
+pub fn part2(input: String, params: &Params) -> u64 {
+    top_calories(input, params.get("k", 3))
+}
+
+fn top_calories(input: String, k: usize) -> u64 {
+    let elves = must_parse(parse, input.as_str());
+    let totals: Vec<u64> = elves.iter().map(Elf::total).collect();
+    sum_top(&top_k(&totals, k))
+}
+
+fn sum_top(top: &TopK<u64>) -> u64 {
+    checked!(
+        top.top
+            .iter()
+            .try_fold(0u64, |sum, (_, total)| sum.checked_add(*total)),
+        "summing the top {} elves' calories",
+        top.top.len()
+    )
+}
+
+#[derive(Debug)]
+pub(super) struct Elf {
+    items: Vec<u64>,
+}
+
+impl Elf {
+    fn total(&self) -> u64 {
+        checked!(
+            self.items
+                .iter()
+                .try_fold(0u64, |total, calories| total.checked_add(*calories)),
+            "summing an elf's calories"
+        )
+    }
+}
+
+// The k largest values, as (index, value) pairs from largest to smallest.
+// Equal values keep their input order, so when the kth place is tied, the
+// earliest values make the cut and the rest are listed as ties.
+#[derive(Debug, PartialEq, Eq)]
+struct TopK<T> {
+    top: Vec<(usize, T)>,
+    // Indices of the values left out of the top k that equal the last value
+    // in it.
+    ties: Vec<usize>,
+}
+
+fn top_k<T: Ord + Copy>(values: &[T], k: usize) -> TopK<T> {
+    let mut top: Vec<(usize, T)> = values
+        .iter()
+        .copied()
+        .enumerate()
+        .sorted_by_key(|(i, value)| (Reverse(*value), *i))
+        .collect();
+    let rest = top.split_off(k.min(top.len()));
+    let ties = match top.last() {
+        Some((_, last)) => rest
+            .iter()
+            .take_while(|(_, value)| value == last)
+            .map(|(i, _)| *i)
+            .collect(),
+        None => Vec::new(),
+    };
+    TopK { top, ties }
+}
+
+// Describes how calories are spread across the elves, and ranks every elf.
+// Elves are numbered from 1 in input order, and tied elves share a rank.
+pub fn report(input: String, params: &Params) -> String {
+    let elves = must_parse(parse, input.as_str());
+    let k = params.get("k", 3);
+    let totals: Vec<u64> = elves.iter().map(Elf::total).collect();
+    let sorted: Vec<u64> = totals.iter().copied().sorted().collect();
+    let median = match sorted.len() {
+        0 => 0.0,
+        n if n % 2 == 1 => sorted[n / 2] as f64,
+        n => (sorted[n / 2 - 1] as f64 + sorted[n / 2] as f64) / 2.0,
+    };
+    let mean = totals.iter().map(|total| *total as f64).sum::<f64>() / totals.len().max(1) as f64;
+
+    let ranking = top_k(&totals, totals.len());
+    let top = top_k(&totals, k);
+    let elf_list = |elves: &mut dyn Iterator<Item = usize>| {
+        let list = elves.map(|i| format!("elf {}", i + 1)).join(", ");
+        if list.is_empty() {
+            "none".to_string()
+        } else {
+            list
+        }
+    };
+    let summary = Summary::new()
+        .field("elves", elves.len())
+        .field(
+            "items",
+            elves.iter().map(|elf| elf.items.len()).sum::<usize>(),
+        )
+        .field("min", sorted.first().copied().unwrap_or(0))
+        .field("max", sorted.last().copied().unwrap_or(0))
+        .field("mean", format!("{:.1}", mean))
+        .field("median", format!("{:.1}", median))
+        .field("top k", k)
+        .field(
+            "top k elves",
+            elf_list(&mut top.top.iter().map(|(i, _)| *i)),
+        )
+        .field("top k calories", sum_top(&top))
+        .field("tied with kth", elf_list(&mut top.ties.iter().copied()));
+
+    let mut report = format!("{}\nrank    elf  items  calories\n", summary);
+    let mut rank = 0;
+    for (place, (i, total)) in ranking.top.iter().enumerate() {
+        if place == 0 || ranking.top[place - 1].1 != *total {
+            rank = place + 1;
+        }
+        report.push_str(&format!(
+            "{:>4}  {:>5}  {:>5}  {:>8}\n",
+            rank,
+            i + 1,
+            elves[*i].items.len(),
+            total
+        ));
+    }
+    report
+}
+
+// Each elf's inventory is a block of calorie counts, one per line.
+pub(super) fn parse(input: &str) -> IResult<'_, Vec<Elf>> {
+    blocks(map(lines(unsigned), |items| Elf { items }))(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{super::shared::catch_failure, *};
+
+    const EXAMPLE_INPUT: &str = "1000
+2000
+3000
+
+4000
+
+5000
+6000
+
+7000
+8000
+9000
+
+10000
+";
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(part1(EXAMPLE_INPUT.into()), 24000)
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(part2(EXAMPLE_INPUT.into(), &Params::new()), 45000);
+        let mut params = Params::new();
+        params.insert("k", "5");
+        assert_eq!(part2(EXAMPLE_INPUT.into(), &params), 55000);
+    }
+
+    #[test]
+    fn test_overflow() {
+        // Too much for a u32, but fine for a u64.
+        assert_eq!(part1("4000000000\n4000000000\n".into()), 8000000000);
+        assert_eq!(
+            catch_failure(|| part1("18446744073709551615\n1\n".into())),
+            Err("Overflow in 2022 day1: summing an elf's calories".into())
+        );
+    }
+
+    #[test]
+    fn test_top_k() {
+        assert_eq!(
+            top_k(&[5, 9, 7, 9, 7, 7], 3),
+            TopK {
+                top: vec![(1, 9), (3, 9), (2, 7)],
+                ties: vec![4, 5],
+            }
+        );
+        assert_eq!(
+            top_k(&[5, 9], 3),
+            TopK {
+                top: vec![(1, 9), (0, 5)],
+                ties: vec![],
+            }
+        );
+        assert_eq!(top_k::<u64>(&[1, 2], 0).top, vec![]);
+    }
+
+    #[test]
+    fn test_report() {
+        let mut params = Params::new();
+        params.insert("k", "2");
+        assert_eq!(
+            report(EXAMPLE_INPUT.replace("10000", "11000"), &params),
+            "\
+elves           5
+items           10
+min             4000
+max             24000
+mean            11200.0
+median          11000.0
+top k           2
+top k elves     elf 4, elf 3
+top k calories  35000
+tied with kth   elf 5
+
+rank    elf  items  calories
+   1      4      3     24000
+   2      3      2     11000
+   2      5      1     11000
+   4      1      3      6000
+   5      2      1      4000
+"
+        );
+    }
+}
@@ -0,0 +1,377 @@
+use nom::{branch::alt, bytes::complete::tag, combinator::map, sequence::preceded};
+
+use super::{
+    inspect::Summary,
+    shared::{
+        image::{Image, ImageOptions},
+        parsing::{lines, signed, IResult},
+    },
+    visualize::Visualize,
+};
+
+pub fn part1(input: String) -> i32 {
+    let instructions = super::shared::must_parse(parse, input.as_str());
+    let states = run_program(instructions);
+
+    let signals_of_interest = states
+        .iter()
+        .map(|state| Cpu {
+            cycle: state.cycle + 1,
+            x: state.x,
+        })
+        .filter(|state| {
+            state.cycle == 20
+                || state.cycle == 60
+                || state.cycle == 100
+                || state.cycle == 140
+                || state.cycle == 180
+                || state.cycle == 220
+        });
+
+    signals_of_interest.map(|Cpu { cycle, x }| cycle * x).sum()
+}
+
+pub fn part2(input: String) -> String {
+    let instructions = super::shared::must_parse(parse, input.as_str());
+    let states = run_program(instructions);
+    draw_crt(&states)
+}
+
+// Draws the pixels for each cycle that has run.
+fn draw_crt(states: &[Cpu]) -> String {
+    let mut message = String::new();
+    let mut sprite_position = 1;
+    for Cpu { cycle, x } in states {
+        let pixel_position = (cycle - 1) % CRT_WIDTH;
+
+        if *cycle > 1 && pixel_position == 0 {
+            message.push('\n')
+        }
+        if pixel_position >= sprite_position - 1 && pixel_position <= sprite_position + 1 {
+            message.push('#')
+        } else {
+            message.push('.')
+        }
+
+        sprite_position = *x;
+    }
+
+    message
+}
+
+const CRT_WIDTH: i32 = 40;
+
+// Draws the CRT's screen, with one image pixel per CRT pixel. Pixels the CRT
+// never reached are left dark.
+pub fn image(input: String, options: &ImageOptions) -> Image {
+    let crt = part2(input);
+    let mut pixels = crt
+        .chars()
+        .filter(|pixel| *pixel != '\n')
+        .map(|pixel| {
+            options
+                .color_map
+                .color(if pixel == '#' { 1.0 } else { 0.0 })
+        })
+        .collect::<Vec<_>>();
+    let width = CRT_WIDTH as usize;
+    pixels.resize(
+        pixels.len().div_ceil(width).max(1) * width,
+        options.color_map.color(0.0),
+    );
+    Image::from_cells(width, pixels).downsample(options.downsample)
+}
+
+pub struct Visualizer {
+    states: Vec<Cpu>,
+    cycles: usize,
+}
+
+pub fn part1_visualizer(input: String) -> Visualizer {
+    Visualizer::new(input.as_str())
+}
+
+pub fn part2_visualizer(input: String) -> Visualizer {
+    Visualizer::new(input.as_str())
+}
+
+impl Visualizer {
+    fn new(input: &str) -> Visualizer {
+        let instructions = super::shared::must_parse(parse, input);
+        Visualizer {
+            states: run_program(instructions),
+            cycles: 0,
+        }
+    }
+}
+
+impl Visualize for Visualizer {
+    fn step(&mut self) -> bool {
+        if self.cycles < self.states.len() {
+            self.cycles += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    // Shows where the sprite is for the next cycle above what the CRT has
+    // drawn so far.
+    fn render(&self) -> String {
+        let x = self.cycles.checked_sub(1).map_or(1, |i| self.states[i].x);
+        let sprite = (0..CRT_WIDTH)
+            .map(|position| if (position - x).abs() <= 1 { '#' } else { '.' })
+            .collect::<String>();
+        format!(
+            "Cycle {} of {}, X = {}\n\nSprite: {}\n\n{}\n",
+            self.cycles,
+            self.states.len(),
+            x,
+            sprite,
+            draw_crt(&self.states[..self.cycles])
+        )
+    }
+}
+
+fn run_program(instructions: Vec<Instruction>) -> Vec<Cpu> {
+    let mut state = Cpu { cycle: 0, x: 1 };
+    instructions
+        .iter()
+        .flat_map(|instruction| match instruction {
+            NoOp => {
+                state.cycle += 1;
+                vec![state.clone()]
+            }
+            AddX(x) => {
+                state.cycle += 1;
+                let c1_state = state.clone();
+                state.cycle += 1;
+                state.x += x;
+                let c2_state = state.clone();
+                vec![c1_state, c2_state]
+            }
+        })
+        .collect()
+}
+
+#[derive(Debug, Clone)]
+struct Cpu {
+    cycle: i32,
+    x: i32,
+}
+
+#[derive(Debug)]
+pub(super) enum Instruction {
+    NoOp,
+    AddX(i32),
+}
+use Instruction::*;
+
+pub fn summarize(input: String) -> Summary {
+    let instructions = super::shared::must_parse(parse, input.as_str());
+    let addxs = instructions
+        .iter()
+        .filter(|instruction| matches!(instruction, AddX(_)))
+        .count();
+    let noops = instructions.len() - addxs;
+    let states = run_program(instructions);
+    Summary::new()
+        .field("instructions", noops + addxs)
+        .field("noop", noops)
+        .field("addx", addxs)
+        .field("cycles", states.len())
+        .field("final X", states.last().map_or(1, |state| state.x))
+}
+
+pub fn dump(input: String) -> String {
+    format!("{:#?}", super::shared::must_parse(parse, input.as_str()))
+}
+
+pub(super) fn parse(input: &str) -> IResult<'_, Vec<Instruction>> {
+    lines(alt((
+        map(tag("noop"), |_| NoOp),
+        map(preceded(tag("addx "), signed), AddX),
+    )))(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{super::shared::catch_failure, *};
+
+    const EXAMPLE_INPUT: &str = "addx 15
+addx -11
+addx 6
+addx -3
+addx 5
+addx -1
+addx -8
+addx 13
+addx 4
+noop
+addx -1
+addx 5
+addx -1
+addx 5
+addx -1
+addx 5
+addx -1
+addx 5
+addx -1
+addx -35
+addx 1
+addx 24
+addx -19
+addx 1
+addx 16
+addx -11
+noop
+noop
+addx 21
+addx -15
+noop
+noop
+addx -3
+addx 9
+addx 1
+addx -3
+addx 8
+addx 1
+addx 5
+noop
+noop
+noop
+noop
+noop
+addx -36
+noop
+addx 1
+addx 7
+noop
+noop
+noop
+addx 2
+addx 6
+noop
+noop
+noop
+noop
+noop
+addx 1
+noop
+noop
+addx 7
+addx 1
+noop
+addx -13
+addx 13
+addx 7
+noop
+addx 1
+addx -33
+noop
+noop
+noop
+addx 2
+noop
+noop
+noop
+addx 8
+noop
+addx -1
+addx 2
+addx 1
+noop
+addx 17
+addx -9
+addx 1
+addx 1
+addx -3
+addx 11
+noop
+noop
+addx 1
+noop
+addx 1
+noop
+noop
+addx -13
+addx -19
+addx 1
+addx 3
+addx 26
+addx -30
+addx 12
+addx -1
+addx 3
+addx 1
+noop
+noop
+noop
+addx -9
+addx 18
+addx 1
+addx 2
+noop
+noop
+addx 9
+noop
+noop
+noop
+addx -1
+addx 2
+addx -37
+addx 1
+addx 3
+noop
+addx 15
+addx -21
+addx 22
+addx -6
+addx 1
+noop
+addx 2
+addx 1
+noop
+addx -10
+noop
+noop
+addx 20
+addx 1
+addx 2
+addx 2
+addx -6
+addx -11
+noop
+noop
+noop
+";
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(part1(EXAMPLE_INPUT.into()), 13140)
+    }
+
+    // Tests build with overflow checks, which panic without saying where,
+    // but failures name the puzzle anyway.
+    #[test]
+    #[cfg(debug_assertions)]
+    fn test_overflow() {
+        assert_eq!(
+            catch_failure(|| part1("addx 2147483647\n".into())),
+            Err("Overflow in 2022 day10: attempt to add with overflow".into())
+        );
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(
+            part2(EXAMPLE_INPUT.into()),
+            "##..##..##..##..##..##..##..##..##..##..
+###...###...###...###...###...###...###.
+####....####....####....####....####....
+#####.....#####.....#####.....#####.....
+######......######......######......####
+#######.......#######.......#######....."
+        )
+    }
+}
@@ -0,0 +1,600 @@
+use std::{
+    cmp::Reverse,
+    collections::{HashMap, VecDeque},
+    fmt::Debug,
+    ops::{Add, Mul},
+};
+
+use itertools::Itertools;
+use nom::{
+    branch::alt,
+    bytes::complete::tag,
+    combinator::map,
+    sequence::{delimited, preceded},
+};
+
+use super::{
+    inspect::Summary,
+    shared::{
+        checked, fail,
+        parsing::{blocks, field, unsigned, unsigned_list, IResult},
+        trace,
+    },
+    visualize::Visualize,
+};
+
+// Worry levels are squared, so they're kept in u64s to have room for squaring
+// any u32.
+pub fn part1(input: String) -> usize {
+    let monkeys: Vec<Monkey<u32>> = super::shared::must_parse(parse, input.as_str());
+    simulate_monkey_business::<u64>(convert_monkeys(monkeys, u64::from), 20, |x| x / 3)
+}
+
+// The trick for part 2 is to use modular arithmetic.
+//
+// We don't care about what the actual item's worry levels are. We only care
+// about whether the worry level is divisible by a particular monkey's
+// divisibility test, because that informs which next monkey will take the item,
+// and we care about how many times a monkey has an item.
+//
+// We take advantage of this by doing all of our arithmetic using _residue
+// numbers_, where we track the residue (remainder) of all operations modulo a
+// set of moduli (in our case, the monkey divisibility test numbers). Since we
+// only care about divisibility, and divisibility is preserved in modular
+// arithmetic with addition and multiplication, this is a safe optimization.
+pub fn part2(input: String) -> usize {
+    let monkeys: Vec<Monkey<u32>> = super::shared::must_parse(parse, input.as_str());
+
+    let residue_monkeys = to_residue_monkeys(monkeys);
+
+    simulate_monkey_business::<ResidueNumber>(residue_monkeys, 10000, |x| x)
+}
+
+fn to_residue_monkeys(monkeys: Vec<Monkey<u32>>) -> Vec<Monkey<ResidueNumber>> {
+    // Build the list of moduli using all the divisibility tests from the
+    // monkeys. These are all the numbers we'll ever care about divisibility
+    // for, so they're the only ones we need to track residues for.
+    let moduli: Vec<u32> = monkeys
+        .iter()
+        .map(|monkey| monkey.divisibility_test)
+        .collect();
+    convert_monkeys(monkeys, |worry_level| {
+        ResidueNumber::new(moduli.as_slice(), worry_level)
+    })
+}
+
+// Changes the type of the monkeys' worry levels.
+fn convert_monkeys<T, F>(monkeys: Vec<Monkey<u32>>, convert: F) -> Vec<Monkey<T>>
+where
+    F: Fn(u32) -> T,
+{
+    monkeys
+        .into_iter()
+        .map(
+            |Monkey {
+                 items,
+                 operation,
+                 divisibility_test,
+                 true_monkey,
+                 false_monkey,
+             }|
+             -> Monkey<T> {
+                // Can't use update syntax yet :( https://github.com/rust-lang/rust/issues/86555
+                Monkey {
+                    items: items.into_iter().map(&convert).collect(),
+                    operation,
+                    divisibility_test,
+                    true_monkey,
+                    false_monkey,
+                }
+            },
+        )
+        .collect()
+}
+
+fn simulate_monkey_business<T>(
+    monkeys: Vec<Monkey<T>>,
+    rounds: usize,
+    update_bored_worry: fn(T) -> T,
+) -> usize
+where
+    T: Worry,
+{
+    let mut troop = Troop::new(monkeys, update_bored_worry);
+    while troop.round < rounds {
+        troop.step();
+    }
+
+    let busiest: Vec<usize> = troop
+        .inspections
+        .iter()
+        .map(Reverse)
+        .k_smallest(2)
+        .map(|n| *n.0)
+        .collect();
+    checked!(
+        busiest
+            .iter()
+            .try_fold(1usize, |product, n| product.checked_mul(*n)),
+        "multiplying inspection counts {:?}",
+        busiest
+    )
+}
+
+// The arithmetic that worry levels need to support.
+trait Worry: TryDivisibleBy<u32> + Clone + Debug {
+    fn add(self, x: u32) -> Self;
+    fn mul(self, x: u32) -> Self;
+    fn square(self) -> Self;
+}
+
+impl Worry for u64 {
+    fn add(self, x: u32) -> u64 {
+        checked!(
+            self.checked_add(x as u64),
+            "adding {} to worry level {}",
+            x,
+            self
+        )
+    }
+
+    fn mul(self, x: u32) -> u64 {
+        checked!(
+            self.checked_mul(x as u64),
+            "multiplying worry level {} by {}",
+            self,
+            x
+        )
+    }
+
+    fn square(self) -> u64 {
+        checked!(self.checked_mul(self), "squaring worry level {}", self)
+    }
+}
+
+impl Worry for ResidueNumber {
+    fn add(self, x: u32) -> ResidueNumber {
+        self + x
+    }
+
+    fn mul(self, x: u32) -> ResidueNumber {
+        self * x
+    }
+
+    fn square(self) -> ResidueNumber {
+        self.clone() * self
+    }
+}
+
+// The monkeys, part of the way through a game of keep away.
+struct Troop<T> {
+    monkeys: Vec<Monkey<T>>,
+    inspections: Vec<usize>,
+    update_bored_worry: fn(T) -> T,
+    // The number of finished rounds, and the monkey whose turn it is.
+    round: usize,
+    turn: usize,
+}
+
+// An item thrown from one monkey to another.
+struct Throw<T> {
+    from: usize,
+    to: usize,
+    worry: T,
+}
+
+impl<T: Worry> Troop<T> {
+    fn new(monkeys: Vec<Monkey<T>>, update_bored_worry: fn(T) -> T) -> Troop<T> {
+        Troop {
+            inspections: vec![0; monkeys.len()],
+            monkeys,
+            update_bored_worry,
+            round: 0,
+            turn: 0,
+        }
+    }
+
+    // The monkey whose turn it is throws its next item. If it has no items
+    // left, its turn ends instead, and the round ends after the last monkey's
+    // turn.
+    fn step(&mut self) -> Option<Throw<T>> {
+        let monkey = &mut self.monkeys[self.turn];
+        let Some(item) = monkey.items.pop_front() else {
+            self.turn += 1;
+            if self.turn == self.monkeys.len() {
+                self.turn = 0;
+                self.round += 1;
+                trace!(
+                    Info,
+                    "round",
+                    round = self.round,
+                    inspections = format!("{:?}", self.inspections),
+                );
+            }
+            return None;
+        };
+
+        let inspection_worry = match monkey.operation {
+            Operation::Add(x) => item.add(x),
+            Operation::Mul(x) => item.mul(x),
+            Operation::Square => item.square(),
+        };
+        let bored_worry = (self.update_bored_worry)(inspection_worry);
+        let target_monkey_index =
+            if let Some(divisible) = bored_worry.clone().divisible_by(monkey.divisibility_test) {
+                if divisible {
+                    monkey.true_monkey
+                } else {
+                    monkey.false_monkey
+                }
+            } else {
+                fail!("Impossible: tried to test divisibility against unsupported modulus")
+            };
+        self.inspections[self.turn] += 1;
+        match self.monkeys.get_mut(target_monkey_index) {
+            Some(target_monkey) => target_monkey.items.push_back(bored_worry.clone()),
+            None => fail!("Invalid: monkey {} threw to non-existent monkey", self.turn),
+        }
+
+        trace!(
+            Debug,
+            "throw",
+            from = self.turn,
+            to = target_monkey_index,
+            worry = format!("{:?}", bored_worry),
+        );
+        Some(Throw {
+            from: self.turn,
+            to: target_monkey_index,
+            worry: bored_worry,
+        })
+    }
+}
+
+pub struct Visualizer<T> {
+    troop: Troop<T>,
+    rounds: usize,
+    last_throw: Option<Throw<T>>,
+    show_worry: fn(&T) -> String,
+}
+
+pub fn part1_visualizer(input: String) -> Visualizer<u64> {
+    let monkeys: Vec<Monkey<u32>> = super::shared::must_parse(parse, input.as_str());
+    Visualizer {
+        troop: Troop::new(convert_monkeys(monkeys, u64::from), |x| x / 3),
+        rounds: 20,
+        last_throw: None,
+        show_worry: u64::to_string,
+    }
+}
+
+// Residue numbers don't have a worry level worth showing, so items are drawn
+// as stars.
+pub fn part2_visualizer(input: String) -> Visualizer<ResidueNumber> {
+    let monkeys: Vec<Monkey<u32>> = super::shared::must_parse(parse, input.as_str());
+    Visualizer {
+        troop: Troop::new(to_residue_monkeys(monkeys), |x| x),
+        rounds: 10000,
+        last_throw: None,
+        show_worry: |_| "*".into(),
+    }
+}
+
+impl<T: Worry> Visualize for Visualizer<T> {
+    fn step(&mut self) -> bool {
+        if self.troop.round < self.rounds {
+            self.last_throw = self.troop.step();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn render(&self) -> String {
+        let mut rendered = format!(
+            "Round {} of {}, monkey {}'s turn\n",
+            (self.troop.round + 1).min(self.rounds),
+            self.rounds,
+            self.troop.turn
+        );
+        if let Some(Throw { from, to, worry }) = &self.last_throw {
+            rendered.push_str(&format!(
+                "Monkey {} threw {} to monkey {}\n",
+                from,
+                (self.show_worry)(worry),
+                to
+            ));
+        }
+        rendered.push('\n');
+        for (i, monkey) in self.troop.monkeys.iter().enumerate() {
+            rendered.push_str(&format!(
+                "{} Monkey {} ({:>6} inspections): {}\n",
+                if i == self.troop.turn { '>' } else { ' ' },
+                i,
+                self.troop.inspections[i],
+                monkey.items.iter().map(self.show_worry).join(", ")
+            ));
+        }
+        rendered
+    }
+}
+
+#[derive(Debug)]
+pub(super) struct Monkey<T> {
+    items: VecDeque<T>,
+    operation: Operation,
+    divisibility_test: u32,
+    true_monkey: usize,
+    false_monkey: usize,
+}
+
+#[derive(Debug)]
+enum Operation {
+    Add(u32),
+    Mul(u32),
+    Square,
+}
+
+// TryDivisibleBy tests divisibility against a divisor, returning the result if
+// the divisor is supported.
+trait TryDivisibleBy<Rhs = Self> {
+    fn divisible_by(self, rhs: Rhs) -> Option<bool>;
+}
+
+impl TryDivisibleBy<u32> for u64 {
+    fn divisible_by(self, rhs: u32) -> Option<bool> {
+        Some(self.is_multiple_of(rhs as u64))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ResidueNumber {
+    residues: HashMap<u32, u32>,
+}
+
+impl ResidueNumber {
+    fn new(moduli: &[u32], x: u32) -> ResidueNumber {
+        let mut n = ResidueNumber {
+            residues: HashMap::new(),
+        };
+        for modulus in moduli {
+            n.residues.insert(*modulus, x % modulus);
+        }
+        modulate(&mut n);
+        n
+    }
+
+    fn moduli(&self) -> Vec<u32> {
+        self.residues.keys().copied().sorted().collect()
+    }
+}
+
+fn modulate(n: &mut ResidueNumber) {
+    let moduli = n.moduli();
+    for modulus in moduli {
+        let v = n
+            .residues
+            .get(&modulus)
+            .unwrap_or_else(|| panic!("impossible: modulus not within residue map"));
+        n.residues.insert(modulus, v % modulus);
+    }
+}
+
+fn binop(f: fn(u32, u32) -> u32, lhs: &ResidueNumber, rhs: &ResidueNumber) -> ResidueNumber {
+    let moduli = lhs.moduli();
+    if moduli != rhs.moduli() {
+        panic!("cannot operate on residue numbers with different moduli");
+    }
+
+    let mut out = ResidueNumber::new(moduli.as_slice(), 0);
+    for modulus in moduli {
+        if let (Some(l), Some(r)) = (lhs.residues.get(&modulus), rhs.residues.get(&modulus)) {
+            out.residues.insert(modulus, f(*l, *r));
+        } else {
+            panic!("impossible: residue numbers with same moduli have inconsistent residue keys")
+        }
+    }
+    modulate(&mut out);
+    out
+}
+
+impl Add for ResidueNumber {
+    type Output = ResidueNumber;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        binop(Add::add, &self, &rhs)
+    }
+}
+
+impl Add<u32> for ResidueNumber {
+    type Output = ResidueNumber;
+
+    fn add(self, rhs: u32) -> Self::Output {
+        let moduli = self.moduli();
+        self + Self::new(moduli.as_slice(), rhs)
+    }
+}
+
+impl Mul for ResidueNumber {
+    type Output = ResidueNumber;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        binop(Mul::mul, &self, &rhs)
+    }
+}
+
+impl Mul<u32> for ResidueNumber {
+    type Output = ResidueNumber;
+
+    fn mul(self, rhs: u32) -> Self::Output {
+        let moduli = self.moduli();
+        self * Self::new(moduli.as_slice(), rhs)
+    }
+}
+
+impl PartialEq for ResidueNumber {
+    fn eq(&self, other: &Self) -> bool {
+        // Note that this works because residues are already modulated as an
+        // invariant.
+        self.residues == other.residues
+    }
+}
+
+// Residue numbers can only be tested for divisibility against one of their
+// moduli.
+//
+// We could implement this for numbers in 0 <= n <= M where M is the product of
+// all moduli if the moduli are pairwise co-prime using the Chinese Remainder
+// Theorem.
+impl TryDivisibleBy<u32> for ResidueNumber {
+    fn divisible_by(self, rhs: u32) -> Option<bool> {
+        self.residues.get(&rhs).map(|n| *n == 0)
+    }
+}
+
+pub fn summarize(input: String) -> Summary {
+    let monkeys: Vec<Monkey<u32>> = super::shared::must_parse(parse, input.as_str());
+    let divisors: Vec<u32> = monkeys
+        .iter()
+        .map(|monkey| monkey.divisibility_test)
+        .collect();
+    // Pairwise co-prime divisors are what would let ResidueNumber use the
+    // Chinese Remainder Theorem for divisibility tests outside its moduli.
+    let pairwise_coprime = divisors
+        .iter()
+        .tuple_combinations()
+        .all(|(a, b)| gcd(*a, *b) == 1);
+    Summary::new()
+        .field("monkeys", monkeys.len())
+        .field(
+            "items",
+            monkeys
+                .iter()
+                .map(|monkey| monkey.items.len())
+                .sum::<usize>(),
+        )
+        .field("divisors", divisors.iter().join(" "))
+        .field("pairwise coprime", pairwise_coprime)
+        .field(
+            "divisor product",
+            divisors.iter().map(|d| *d as u64).product::<u64>(),
+        )
+}
+
+fn gcd(a: u32, b: u32) -> u32 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+pub fn dump(input: String) -> String {
+    format!(
+        "{:#?}",
+        super::shared::must_parse::<_, Vec<Monkey<u32>>>(parse, input.as_str())
+    )
+}
+
+pub(super) fn parse(input: &str) -> IResult<'_, Vec<Monkey<u32>>> {
+    blocks(parse_monkey)(input)
+}
+
+fn parse_monkey(input: &str) -> IResult<'_, Monkey<u32>> {
+    let (input, _) = delimited(tag("Monkey "), unsigned::<u32>, tag(":\n"))(input)?;
+    let (input, items) = field(
+        "  Starting items: ",
+        map(unsigned_list(", "), |xs: Vec<u32>| xs.into_iter().collect()),
+    )(input)?;
+    let (input, operation) = field(
+        "  Operation: new = old ",
+        alt((
+            map(preceded(tag("+ "), unsigned), Operation::Add),
+            map(preceded(tag("* "), unsigned), Operation::Mul),
+            map(tag("* old"), |_| Operation::Square),
+        )),
+    )(input)?;
+    let (input, divisibility_test) = field("  Test: divisible by ", unsigned)(input)?;
+    let (input, true_monkey) = field("    If true: throw to monkey ", unsigned)(input)?;
+    let (input, false_monkey) = field("    If false: throw to monkey ", unsigned)(input)?;
+
+    Ok((
+        input,
+        Monkey {
+            items,
+            operation,
+            divisibility_test,
+            true_monkey,
+            false_monkey,
+        },
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{super::shared::catch_failure, *};
+
+    const EXAMPLE_INPUT: &str = "Monkey 0:
+  Starting items: 79, 98
+  Operation: new = old * 19
+  Test: divisible by 23
+    If true: throw to monkey 2
+    If false: throw to monkey 3
+
+Monkey 1:
+  Starting items: 54, 65, 75, 74
+  Operation: new = old + 6
+  Test: divisible by 19
+    If true: throw to monkey 2
+    If false: throw to monkey 0
+
+Monkey 2:
+  Starting items: 79, 60, 97
+  Operation: new = old * old
+  Test: divisible by 13
+    If true: throw to monkey 1
+    If false: throw to monkey 3
+
+Monkey 3:
+  Starting items: 74
+  Operation: new = old + 3
+  Test: divisible by 17
+    If true: throw to monkey 0
+    If false: throw to monkey 1
+";
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(part1(EXAMPLE_INPUT.into()), 10605)
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(part2(EXAMPLE_INPUT.into()), 2713310158)
+    }
+
+    #[test]
+    fn test_overflow() {
+        // Squaring anything over 65535 overflows a u32, but not a u64.
+        assert_eq!(Worry::square(70000u64), 4900000000);
+        // Squaring again (and again) overflows even a u64.
+        let input = EXAMPLE_INPUT.replace("79, 60, 97", "70000");
+        assert_eq!(
+            catch_failure(|| part1(input)),
+            Err("Overflow in 2022 day11: squaring worry level 2575205945537890".into())
+        );
+    }
+
+    #[test]
+    fn test_summarize() {
+        assert_eq!(
+            summarize(EXAMPLE_INPUT.into()),
+            Summary::new()
+                .field("monkeys", 4)
+                .field("items", 10)
+                .field("divisors", "23 19 13 17")
+                .field("pairwise coprime", true)
+                .field("divisor product", 96577)
+        )
+    }
+}
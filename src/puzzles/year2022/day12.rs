@@ -0,0 +1,344 @@
+use std::collections::{hash_map::Entry, HashMap};
+
+use petgraph::prelude::DiGraphMap;
+
+use super::{
+    explore::{expect_args, Explore},
+    inspect::Summary,
+    shared::{
+        fail,
+        geometry::Point,
+        grid::{self, Grid},
+        image::{Image, ImageOptions, Rgb},
+        must_parse,
+        parsing::char_grid,
+        trace,
+    },
+};
+
+pub fn part1(input: String) -> u32 {
+    let (start, goal, _, graph) = parse(input.as_str());
+    let paths = distances(&graph, start, Some(goal));
+    *paths
+        .get(&goal)
+        .unwrap_or_else(|| fail!("Impossible: no path from start to goal"))
+}
+
+pub fn part2(input: String) -> u32 {
+    // Take the original edges, and reverse all of them.
+    let (_, goal, heights, graph) = parse(input.as_str());
+    let reverse_graph = DiGraphMap::from_edges(graph.all_edges().map(|(a, b, ())| (b, a, ())));
+
+    // Find the shortest path to any trail starting point.
+    let paths = distances(&reverse_graph, goal, None);
+    heights
+        .iter()
+        .filter(|(_, height)| **height == 1)
+        .filter_map(|(position, _)| paths.get(&point(position)))
+        .copied()
+        .min()
+        .unwrap_or_else(|| fail!("Impossible: no paths from hilltop to hiking trail starts"))
+}
+
+// Finds the distance to every position reachable from a position, by searching
+// breadth-first, since every step costs the same. Stops once the goal (if
+// any) has been reached.
+fn distances(
+    graph: &DiGraphMap<Position, ()>,
+    from: Position,
+    goal: Option<Position>,
+) -> HashMap<Position, u32> {
+    let mut distances = HashMap::from([(from, 0)]);
+    let mut frontier = vec![from];
+    let mut distance = 0;
+    while !frontier.is_empty() && goal.is_none_or(|goal| !distances.contains_key(&goal)) {
+        trace!(
+            Debug,
+            "frontier",
+            distance = distance,
+            size = frontier.len()
+        );
+        distance += 1;
+        let mut next = Vec::new();
+        for position in frontier {
+            for neighbor in graph.neighbors(position) {
+                if let Entry::Vacant(entry) = distances.entry(neighbor) {
+                    trace!(Trace, "expand", from = position, to = neighbor,);
+                    entry.insert(distance);
+                    next.push(neighbor);
+                }
+            }
+        }
+        frontier = next;
+    }
+    distances
+}
+
+pub struct Explorer {
+    start: Position,
+    goal: Position,
+    heights: Grid<Height>,
+    graph: DiGraphMap<Position, ()>,
+}
+
+impl Explorer {
+    pub fn new(input: String) -> Explorer {
+        let (start, goal, heights, graph) = parse(input.as_str());
+        Explorer {
+            start,
+            goal,
+            heights,
+            graph,
+        }
+    }
+
+    // Positions are written as x,y, or as S or E for the start and goal.
+    fn position(&self, arg: &str) -> Result<Position, String> {
+        let position = match arg {
+            "S" => self.start,
+            "E" => self.goal,
+            _ => arg
+                .split_once(',')
+                .and_then(|(x, y)| Some(Point::new(x.parse().ok()?, y.parse().ok()?)))
+                .ok_or_else(|| format!("Invalid position (expected x,y, S or E): {}", arg))?,
+        };
+        if self.height(position).is_some() {
+            Ok(position)
+        } else {
+            Err(format!("Position outside of heightmap: {}", position))
+        }
+    }
+
+    fn height(&self, position: Position) -> Option<Height> {
+        self.heights.get(position.to_unsigned()?).copied()
+    }
+}
+
+impl Explore for Explorer {
+    fn commands(&self) -> Vec<(&'static str, &'static str)> {
+        vec![
+            ("height <x,y>", "Height of a position, from 1 (a) to 26 (z)"),
+            (
+                "distance <x,y> <x,y>",
+                "Fewest steps to climb from one position to another",
+            ),
+        ]
+    }
+
+    fn run(&self, command: &str, args: &[&str]) -> Option<Result<String, String>> {
+        match command {
+            "height" => Some(
+                expect_args(args, "height <x,y>")
+                    .and_then(|[position]| self.position(position))
+                    .map(|position| self.height(position).unwrap_or_default().to_string()),
+            ),
+            "distance" => Some(
+                expect_args(args, "distance <x,y> <x,y>").and_then(|[from, to]| {
+                    let (from, to) = (self.position(from)?, self.position(to)?);
+                    let paths = distances(&self.graph, from, Some(to));
+                    Ok(match paths.get(&to) {
+                        Some(distance) => distance.to_string(),
+                        None => format!("No path from {} to {}", from, to),
+                    })
+                }),
+            ),
+            _ => None,
+        }
+    }
+}
+
+type Height = u32;
+
+type Position = Point;
+
+// Converts a heightmap position to a point. Heightmaps are never big enough
+// for this to fail.
+fn point(position: grid::Position) -> Position {
+    Point::from_unsigned(position)
+        .unwrap_or_else(|| fail!("Heightmap position out of range: {:?}", position))
+}
+
+// Colors the heightmap by height, and draws the shortest route to the goal in
+// red.
+pub fn part1_image(input: String, options: &ImageOptions) -> Image {
+    let (start, goal, heights, graph) = parse(input.as_str());
+    draw_route(&heights, &graph, |_| start, goal, options)
+}
+
+pub fn part2_image(input: String, options: &ImageOptions) -> Image {
+    let (_, goal, heights, graph) = parse(input.as_str());
+    draw_route(
+        &heights,
+        &graph,
+        |distances| {
+            // Start from the lowest square closest to the goal.
+            heights
+                .iter()
+                .filter(|(_, height)| **height == 1)
+                .filter_map(|(position, _)| {
+                    let position = point(position);
+                    Some((distances.get(&position)?, position))
+                })
+                .min()
+                .map_or(goal, |(_, position)| position)
+        },
+        goal,
+        options,
+    )
+}
+
+fn draw_route<F>(
+    heights: &Grid<Height>,
+    graph: &DiGraphMap<Position, ()>,
+    choose_start: F,
+    goal: Position,
+    options: &ImageOptions,
+) -> Image
+where
+    F: FnOnce(&HashMap<Position, u32>) -> Position,
+{
+    // Find every square's distance to the goal by searching backwards from it,
+    // then walk downhill in distance from the start.
+    let reverse_graph =
+        DiGraphMap::<Position, ()>::from_edges(graph.all_edges().map(|(a, b, ())| (b, a, ())));
+    let distances = distances(&reverse_graph, goal, None);
+    let mut position = choose_start(&distances);
+    let mut route = vec![position];
+    while let Some(distance) = distances.get(&position).filter(|distance| **distance > 0) {
+        match graph
+            .neighbors(position)
+            .find(|next| distances.get(next) == Some(&(distance - 1)))
+        {
+            Some(next) => {
+                position = next;
+                route.push(next);
+            }
+            None => break,
+        }
+    }
+
+    let mut image = heights.map(|height| options.color_map.scaled(*height as f64, 1.0, 26.0));
+    for position in route.iter().filter_map(|position| position.to_unsigned()) {
+        image[position] = Rgb::RED;
+    }
+    image.downsample(options.downsample)
+}
+
+pub fn summarize(input: String) -> Summary {
+    let (start, goal, heights, graph) = parse(input.as_str());
+    Summary::new()
+        .field("width", heights.width())
+        .field("height", heights.height())
+        .field("start", start)
+        .field("goal", goal)
+        .field(
+            "lowest squares",
+            heights.values().filter(|height| **height == 1).count(),
+        )
+        .field("climbable edges", graph.edge_count())
+}
+
+pub fn dump(input: String) -> String {
+    let (start, goal, heights, _) = parse(input.as_str());
+    format!("start: {}\ngoal: {}\nheights: {:#?}", start, goal, heights)
+}
+
+pub(super) fn parse(input: &str) -> (Position, Position, Grid<Height>, DiGraphMap<Position, ()>) {
+    // First, we parse to a Grid<Height>.
+    let mut start = None;
+    let mut goal = None;
+    let heights = must_parse(
+        char_grid(|position, c| {
+            if c.is_ascii_lowercase() {
+                Some((c as u32) - 96)
+            } else if c == 'S' {
+                start = Some(point(position));
+                Some(1)
+            } else if c == 'E' {
+                goal = Some(point(position));
+                Some(26)
+            } else {
+                None
+            }
+        }),
+        input,
+    );
+
+    // Then we construct a directed graph from the map, where edge (A, B) exists
+    // if height(B) <= height(A) + 1.
+    let mut graph = DiGraphMap::new();
+    for (position, height) in heights.iter() {
+        graph.add_node(point(position));
+        for neighbor in heights.neighbors4(position) {
+            graph.add_node(point(neighbor));
+            if heights[neighbor] <= *height + 1 {
+                graph.add_edge(point(position), point(neighbor), ());
+            }
+        }
+    }
+
+    (
+        match start {
+            Some(s) => s,
+            None => {
+                fail!("Invalid puzzle: no start position detected")
+            }
+        },
+        match goal {
+            Some(e) => e,
+            None => {
+                fail!("Invalid puzzle: no end position detected")
+            }
+        },
+        heights,
+        graph,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE_INPUT: &str = "Sabqponm
+abcryxxl
+accszExk
+acctuvwj
+abdefghi
+";
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(part1(EXAMPLE_INPUT.into()), 31)
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(part2(EXAMPLE_INPUT.into()), 29)
+    }
+
+    #[test]
+    fn test_summarize() {
+        assert_eq!(
+            summarize(EXAMPLE_INPUT.into()),
+            Summary::new()
+                .field("width", 8)
+                .field("height", 5)
+                .field("start", "0,0")
+                .field("goal", "5,2")
+                .field("lowest squares", 6)
+                .field("climbable edges", 111)
+        )
+    }
+
+    #[test]
+    fn test_explorer() {
+        let explorer = Explorer::new(EXAMPLE_INPUT.into());
+        assert_eq!(explorer.run("distance", &["S", "E"]), Some(Ok("31".into())));
+        assert_eq!(
+            explorer.run("distance", &["0,4", "E"]),
+            Some(Ok("29".into()))
+        );
+        assert_eq!(explorer.run("height", &["5,2"]), Some(Ok("26".into())));
+        assert!(matches!(explorer.run("height", &["8,0"]), Some(Err(_))));
+    }
+}
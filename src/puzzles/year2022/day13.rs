@@ -0,0 +1,277 @@
+use std::{
+    cmp::{Ordering, Ordering::*},
+    fmt::{self, Display, Formatter},
+};
+
+use itertools::Itertools;
+use nom::{
+    branch::alt,
+    character::complete::{char, newline},
+    combinator::map,
+    multi::separated_list0,
+    sequence::{delimited, pair, terminated},
+};
+
+use super::{
+    explore::{expect_args, Explore},
+    inspect::Summary,
+    shared::{
+        fail,
+        parsing::{blocks, parse_complete, unsigned, IResult},
+    },
+};
+
+pub fn part1(input: String) -> usize {
+    let pairs = super::shared::must_parse(parse, input.as_str());
+
+    pairs
+        .into_iter()
+        .map(|(left, right)| {
+            left.partial_cmp(&right).unwrap_or_else(|| {
+                fail!("Impossible: packet pair has ambiguous ordering: {left:?} {right:?}")
+            }) == Less
+        })
+        .enumerate()
+        .filter(|(_, in_order)| *in_order)
+        .map(|(i, _)| i + 1)
+        .sum()
+}
+
+pub fn part2(input: String) -> usize {
+    let divider1 = List(vec![List(vec![Int(2)])]);
+    let divider2 = List(vec![List(vec![Int(6)])]);
+    let pairs = super::shared::must_parse(parse, input.as_str());
+    let packets = {
+        let mut packets: Vec<Packet> = pairs.into_iter().flat_map(|(a, b)| [a, b]).collect();
+        packets.push(divider1.clone());
+        packets.push(divider2.clone());
+        packets
+    };
+    packets
+        .into_iter()
+        .sorted_by(|l, r| {
+            l.partial_cmp(r).unwrap_or_else(|| {
+                fail!("Impossible: packets have ambiguous ordering: {l:?} {r:?}")
+            })
+        })
+        .enumerate()
+        .filter(|(_, p)| *p == divider1 || *p == divider2)
+        .map(|(i, _)| i + 1)
+        .product()
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) enum Packet {
+    List(Vec<Packet>),
+    Int(u32),
+}
+
+use Packet::*;
+
+impl PartialOrd for Packet {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        match (self, other) {
+            (Int(l), List(_)) => Packet::partial_cmp(&List(vec![Int(*l)]), other),
+            (List(_), Int(r)) => Packet::partial_cmp(self, &List(vec![Int(*r)])),
+            (Int(l), Int(r)) => match l.cmp(r) {
+                Equal => None,
+                c => Some(c),
+            },
+            (List(l), List(r)) => {
+                for i in 0.. {
+                    match (l.get(i), r.get(i)) {
+                        (Some(x), Some(y)) => match Packet::partial_cmp(x, y) {
+                            Some(result) => return Some(result),
+                            None => continue,
+                        },
+                        (None, Some(_)) => return Some(Less),
+                        (Some(_), None) => return Some(Greater),
+                        (None, None) => return None,
+                    }
+                }
+                panic!("impossible: list matching loop completed without reaching empty case")
+            }
+        }
+    }
+}
+
+pub struct Explorer {
+    pairs: Vec<(Packet, Packet)>,
+}
+
+impl Explorer {
+    pub fn new(input: String) -> Explorer {
+        Explorer {
+            pairs: super::shared::must_parse(parse, input.as_str()),
+        }
+    }
+}
+
+fn describe_order(left: &Packet, right: &Packet) -> &'static str {
+    match left.partial_cmp(right) {
+        Some(Less) => "in order",
+        Some(Greater) => "out of order",
+        Some(Equal) | None => "ambiguous",
+    }
+}
+
+fn parse_packet_arg(arg: &str) -> Result<Packet, String> {
+    parse_complete(packet_list, arg).map_err(|err| format!("Invalid packet {}: {}", arg, err))
+}
+
+impl Explore for Explorer {
+    fn commands(&self) -> Vec<(&'static str, &'static str)> {
+        vec![
+            ("pair <n>", "Show the nth pair of packets and their order"),
+            (
+                "compare <packet> <packet>",
+                "Compare two packets, like [1,[2]] [3]",
+            ),
+        ]
+    }
+
+    fn run(&self, command: &str, args: &[&str]) -> Option<Result<String, String>> {
+        match command {
+            "pair" => Some(expect_args(args, "pair <n>").and_then(|[n]| {
+                let (left, right) = n
+                    .parse::<usize>()
+                    .ok()
+                    .and_then(|n| self.pairs.get(n.checked_sub(1)?))
+                    .ok_or_else(|| {
+                        format!("No such pair (there are {}): {}", self.pairs.len(), n)
+                    })?;
+                Ok(format!(
+                    "{}\n{}\n{}",
+                    left,
+                    right,
+                    describe_order(left, right)
+                ))
+            })),
+            "compare" => Some(expect_args(args, "compare <packet> <packet>").and_then(
+                |[left, right]| {
+                    let (left, right) = (parse_packet_arg(left)?, parse_packet_arg(right)?);
+                    Ok(describe_order(&left, &right).to_string())
+                },
+            )),
+            _ => None,
+        }
+    }
+}
+
+impl Display for Packet {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Int(n) => write!(f, "{}", n),
+            List(packets) => write!(f, "[{}]", packets.iter().join(",")),
+        }
+    }
+}
+
+pub fn summarize(input: String) -> Summary {
+    let pairs = super::shared::must_parse(parse, input.as_str());
+    let packets = pairs.iter().flat_map(|(a, b)| [a, b]);
+    Summary::new()
+        .field("pairs", pairs.len())
+        .field("packets", pairs.len() * 2)
+        .field(
+            "deepest nesting",
+            packets.clone().map(Packet::depth).max().unwrap_or(0),
+        )
+        .field(
+            "largest integer",
+            packets.filter_map(Packet::max_int).max().unwrap_or(0),
+        )
+}
+
+impl Packet {
+    fn depth(&self) -> usize {
+        match self {
+            Int(_) => 0,
+            List(packets) => 1 + packets.iter().map(Packet::depth).max().unwrap_or(0),
+        }
+    }
+
+    fn max_int(&self) -> Option<u32> {
+        match self {
+            Int(n) => Some(*n),
+            List(packets) => packets.iter().filter_map(Packet::max_int).max(),
+        }
+    }
+}
+
+pub fn dump(input: String) -> String {
+    format!("{:#?}", super::shared::must_parse(parse, input.as_str()))
+}
+
+pub(super) fn parse(input: &str) -> IResult<'_, Vec<(Packet, Packet)>> {
+    blocks(pair(packet_outer, packet_outer))(input)
+}
+
+fn packet_outer(input: &str) -> IResult<'_, Packet> {
+    terminated(packet_list, newline)(input)
+}
+
+fn packet(input: &str) -> IResult<'_, Packet> {
+    alt((map(unsigned, Int), packet_list))(input)
+}
+
+fn packet_list(input: &str) -> IResult<'_, Packet> {
+    map(
+        delimited(char('['), separated_list0(char(','), packet), char(']')),
+        List,
+    )(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE_INPUT: &str = "[1,1,3,1,1]
+[1,1,5,1,1]
+
+[[1],[2,3,4]]
+[[1],4]
+
+[9]
+[[8,7,6]]
+
+[[4,4],4,4]
+[[4,4],4,4,4]
+
+[7,7,7,7]
+[7,7,7]
+
+[]
+[3]
+
+[[[]]]
+[[]]
+
+[1,[2,[3,[4,[5,6,7]]]],8,9]
+[1,[2,[3,[4,[5,6,0]]]],8,9]
+";
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(part1(EXAMPLE_INPUT.into()), 13)
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(part2(EXAMPLE_INPUT.into()), 140)
+    }
+
+    #[test]
+    fn test_explorer() {
+        let explorer = Explorer::new(EXAMPLE_INPUT.into());
+        assert_eq!(
+            explorer.run("pair", &["2"]),
+            Some(Ok("[[1],[2,3,4]]\n[[1],4]\nin order".into()))
+        );
+        assert_eq!(
+            explorer.run("compare", &["[9]", "[[8,7,6]]"]),
+            Some(Ok("out of order".into()))
+        );
+        assert!(matches!(explorer.run("pair", &["9"]), Some(Err(_))));
+    }
+}
@@ -0,0 +1,298 @@
+use itertools::Itertools;
+use nom::{
+    bytes::complete::tag, character::complete::char, combinator::map, multi::separated_list1,
+    sequence::separated_pair,
+};
+
+use super::{
+    inspect::Summary,
+    shared::{
+        fail,
+        geometry::{BoundingBox, Point, Vector},
+        grid::SparseGrid,
+        image::{Image, ImageOptions, Rgb},
+        parsing::{lines, signed, IResult},
+    },
+    visualize::Visualize,
+};
+
+const SAND_SOURCE: Position = Point::new(500, 0);
+
+// Sand tries to fall straight down, then diagonally down-left, then
+// diagonally down-right.
+const FALLS: [Vector; 3] = [Vector::new(0, 1), Vector::new(-1, 1), Vector::new(1, 1)];
+
+pub fn part1(input: String) -> usize {
+    simulate_sand(input.as_str(), add_sand_bottomless)
+}
+
+pub fn part2(input: String) -> usize {
+    simulate_sand(input.as_str(), add_sand_floored)
+}
+
+// Adds a unit of sand to the cave, returning where it settles, or None once
+// no more sand can settle.
+type AddSand = fn(&Cave, i32) -> Option<Position>;
+
+fn simulate_sand(input: &str, add_sand_until: AddSand) -> usize {
+    let mut sands = Sands::new(input, add_sand_until);
+    while sands.step() {}
+    sands.count
+}
+
+// A cave that sand is pouring into.
+struct Sands {
+    cave: Cave,
+    bottom_rock_y: i32,
+    add_sand_until: AddSand,
+    count: usize,
+}
+
+impl Sands {
+    fn new(input: &str, add_sand_until: AddSand) -> Sands {
+        // Parse the cave.
+        let cave = super::shared::must_parse(parse, input);
+
+        // Pre-calculate rock bottom.
+        let bottom_rock_y = cave
+            .iter()
+            .filter(|(_, tile)| **tile == Rock)
+            .map(|(position, _)| position.y)
+            .max()
+            .unwrap_or_else(|| fail!("Invalid: cave had no rocks"));
+
+        Sands {
+            cave,
+            bottom_rock_y,
+            add_sand_until,
+            count: 0,
+        }
+    }
+
+    // Simulates one unit of sand falling. Returns false once the stop
+    // condition is reached.
+    fn step(&mut self) -> bool {
+        match (self.add_sand_until)(&self.cave, self.bottom_rock_y) {
+            Some(new_sand) => {
+                self.cave.insert(new_sand, Sand);
+                self.count += 1;
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+// Draws rock and sand at rest once the sand stops, one pixel per tile.
+pub fn part1_image(input: String, options: &ImageOptions) -> Image {
+    draw_sands(input.as_str(), add_sand_bottomless, options)
+}
+
+pub fn part2_image(input: String, options: &ImageOptions) -> Image {
+    draw_sands(input.as_str(), add_sand_floored, options)
+}
+
+fn draw_sands(input: &str, add_sand_until: AddSand, options: &ImageOptions) -> Image {
+    let mut sands = Sands::new(input, add_sand_until);
+    while sands.step() {}
+
+    let bounds = BoundingBox::of(
+        sands
+            .cave
+            .bounds()
+            .into_iter()
+            .flat_map(|bounds| [bounds.min, bounds.max])
+            .chain([SAND_SOURCE]),
+    )
+    .unwrap_or_else(|| BoundingBox::new(SAND_SOURCE));
+    let air = options.color_map.color(0.0);
+    let mut image = Image::new(bounds.width() as usize, bounds.height() as usize, air);
+    for (position, tile) in sands.cave.iter() {
+        let color = match tile {
+            Rock => Rgb(110, 110, 120),
+            Sand => Rgb(230, 190, 90),
+        };
+        if let Some(pixel) = (position - (bounds.min - Point::ORIGIN)).to_unsigned() {
+            image[pixel] = color;
+        }
+    }
+    image.downsample(options.downsample)
+}
+
+pub struct Visualizer(Sands);
+
+pub fn part1_visualizer(input: String) -> Visualizer {
+    Visualizer(Sands::new(input.as_str(), add_sand_bottomless))
+}
+
+pub fn part2_visualizer(input: String) -> Visualizer {
+    Visualizer(Sands::new(input.as_str(), add_sand_floored))
+}
+
+impl Visualize for Visualizer {
+    fn step(&mut self) -> bool {
+        self.0.step()
+    }
+
+    fn render(&self) -> String {
+        format!(
+            "{} units of sand at rest\n\n{}",
+            self.0.count,
+            render_cave(&self.0.cave)
+        )
+    }
+}
+
+fn add_sand_bottomless(cave: &Cave, bottom_rock_y: i32) -> Option<Position> {
+    // Take steps until the sand settles. If the sand goes past the lowest rock
+    // level, it falls into the abyss.
+    let mut sand_position = SAND_SOURCE;
+    loop {
+        if sand_position.y > bottom_rock_y {
+            // Once a sand has fallen into the abyss, all subsequent sands will
+            // also fall into the abyss because the simulation is deterministic.
+            // Stop simulating sand.
+            return None;
+        } else if let Some(next_position) = fall(cave, sand_position) {
+            sand_position = next_position
+        } else {
+            return Some(sand_position);
+        }
+    }
+}
+
+fn add_sand_floored(cave: &Cave, bottom_rock_y: i32) -> Option<Position> {
+    // Stop simulating sand once the source is blocked.
+    if let Some(Sand) = cave.get(SAND_SOURCE) {
+        return None;
+    }
+
+    // Take steps until the sand settles.
+    let mut sand_position = SAND_SOURCE;
+    loop {
+        if sand_position.y == bottom_rock_y + 1 {
+            return Some(sand_position);
+        } else if let Some(next_position) = fall(cave, sand_position) {
+            sand_position = next_position
+        } else {
+            return Some(sand_position);
+        }
+    }
+}
+
+fn fall(cave: &Cave, sand_position: Position) -> Option<Position> {
+    FALLS
+        .iter()
+        .map(|fall| sand_position + *fall)
+        .find(|position| !cave.contains(*position))
+}
+
+// Positive directions are (rightwards, downwards).
+type Position = Point;
+
+// Map from a position to whether it's occupied. Both sand and rock count as
+// occupied, and air does not.
+type Cave = SparseGrid<Tile>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub(super) enum Tile {
+    Rock,
+    Sand,
+}
+use Tile::*;
+
+fn render_cave(cave: &Cave) -> String {
+    cave.render(&[SAND_SOURCE], |position, tile| match tile {
+        Some(Rock) => '#',
+        Some(Sand) => 'o',
+        None if position == SAND_SOURCE => '+',
+        None => '.',
+    })
+}
+
+pub fn summarize(input: String) -> Summary {
+    let cave = super::shared::must_parse(parse, input.as_str());
+    let bounds = cave
+        .bounds()
+        .unwrap_or_else(|| BoundingBox::new(Point::ORIGIN));
+    Summary::new()
+        .field("rock tiles", cave.len())
+        .field("x range", format!("{}..={}", bounds.min.x, bounds.max.x))
+        .field("y range", format!("{}..={}", bounds.min.y, bounds.max.y))
+}
+
+pub fn dump(input: String) -> String {
+    let cave = super::shared::must_parse(parse, input.as_str());
+    format!("{:#?}", cave.iter().sorted().collect::<Vec<_>>())
+}
+
+pub(super) fn parse(input: &str) -> IResult<'_, Cave> {
+    let (remaining, paths) = lines(separated_list1(
+        tag(" -> "),
+        map(separated_pair(signed, char(','), signed), Point::from),
+    ))(input)?;
+    Ok((
+        remaining,
+        SparseGrid::from_iter(
+            paths
+                .into_iter()
+                .flat_map(|endpoints| {
+                    endpoints
+                        .windows(2)
+                        .flat_map(|ends| match ends {
+                            // Paths are axis-aligned, so the box spanning two
+                            // endpoints is the line between them.
+                            [a, b] => BoundingBox::new(*a).including(*b).points(),
+                            _ => {
+                                fail!("Impossible: endpoint windows did not pattern match")
+                            }
+                        })
+                        .collect::<Vec<_>>()
+                })
+                .map(|point| (point, Rock)),
+        ),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE_INPUT: &str = "498,4 -> 498,6 -> 496,6
+503,4 -> 502,4 -> 502,9 -> 494,9
+";
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(part1(EXAMPLE_INPUT.into()), 24)
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(part2(EXAMPLE_INPUT.into()), 93)
+    }
+
+    #[test]
+    fn test_visualizer() {
+        let mut visualizer = part1_visualizer(EXAMPLE_INPUT.into());
+        for _ in 0..5 {
+            visualizer.step();
+        }
+        assert_eq!(
+            visualizer.render(),
+            "5 units of sand at rest
+
+......+...
+..........
+..........
+..........
+....#...##
+....#...#.
+..###...#.
+......o.#.
+....oooo#.
+#########.
+"
+        );
+    }
+}
@@ -0,0 +1,286 @@
+use itertools::Itertools;
+use nom::{
+    bytes::complete::tag,
+    combinator::map,
+    sequence::{separated_pair, tuple},
+};
+
+use super::{
+    inspect::Summary,
+    params::Params,
+    shared::{
+        fail,
+        geometry::{BoundingBox, Point, Vector},
+        grid::Grid,
+        image::{Image, ImageOptions, Rgb},
+        parsing::{key_value, lines, signed, IResult},
+        range_set::RangeSet,
+    },
+};
+
+pub fn part1(input: String, params: &Params) -> usize {
+    part1_solve(params.get("row", 2_000_000), input.as_str())
+}
+
+fn part1_solve(target_row: i32, input: &str) -> usize {
+    let sensors = super::shared::must_parse(parse, input);
+
+    // For each sensor, compute the range of excluded positions in the target
+    // row. A sensor covers less of a row the further the row is from it.
+    let covered = sensors
+        .iter()
+        .filter_map(|sensor| {
+            let distance = sensor.position.manhattan(sensor.closest_beacon);
+            let reach = distance - (sensor.position.y - target_row).abs();
+            (reach >= 0).then(|| sensor.position.x - reach..=sensor.position.x + reach)
+        })
+        .collect::<RangeSet<_>>();
+
+    // Positions with a known beacon can't be excluded.
+    let beacons = sensors
+        .iter()
+        .map(|sensor| sensor.closest_beacon)
+        .filter(|beacon| beacon.y == target_row)
+        .map(|beacon| beacon.x..=beacon.x)
+        .collect::<RangeSet<_>>();
+
+    covered.difference(&beacons).len() as usize
+}
+
+pub fn part2(input: String, params: &Params) -> i64 {
+    part2_solve(params.get("search_area", 4_000_000), input.as_str())
+}
+
+fn part2_solve(search_area: i32, input: &str) -> i64 {
+    let sensors = super::shared::must_parse(parse, input)
+        .into_iter()
+        .map(|sensor| {
+            let distance = sensor.position.manhattan(sensor.closest_beacon);
+            (sensor, distance)
+        })
+        .collect::<Vec<_>>();
+
+    // The key insight: since there's only a single possible position for a
+    // distress beacon, that position must be exactly one tile just outside a
+    // covered position. Otherwise, there would be multiple possible positions
+    // for a distress beacon!
+    //
+    // Rather than searching the entire space, we can instead limit our search
+    // to all tiles that are just outside covered sensor range, which is equal
+    // to all tiles that are distance+1 from a sensor.
+    let candidates = sensors
+        .clone()
+        .into_iter()
+        .flat_map(|(Sensor { position, .. }, distance)| {
+            // Map a sensor to the set of boundary tiles that are candidates for
+            // the distress beacon's position.
+            //
+            // We could map this to a set and union the set, but that turns out
+            // to be much slower (~750s vs. ~7s) than just iterating over the
+            // extra ~3M elements.
+            let mut candidates = Vec::new();
+            let Point { x, y } = position;
+            let left_bound = x - (distance + 1);
+            for i in left_bound..=x {
+                candidates.push(Point::new(i, y + (i - left_bound)));
+                candidates.push(Point::new(i, y - (i - left_bound)));
+            }
+            let right_bound = x + (distance + 1);
+            for i in x..=right_bound {
+                candidates.push(Point::new(i, y + (right_bound - i)));
+                candidates.push(Point::new(i, y - (right_bound - i)));
+            }
+            candidates
+                .into_iter()
+                .filter(|Point { x, y }| *x > 0 && *x <= search_area && *y > 0 && *y <= search_area)
+        })
+        .collect::<Vec<_>>();
+
+    'search: for candidate in candidates {
+        for (Sensor { position, .. }, distance) in &sensors {
+            if position.manhattan(candidate) <= *distance {
+                continue 'search;
+            }
+        }
+        let Point { x, y } = candidate;
+        return (x as i64) * 4_000_000 + (y as i64);
+    }
+    fail!("Invalid: no valid distress beacon positions")
+}
+
+type Position = Point;
+
+// Real inputs span millions of positions, so the image is always downsampled
+// enough to fit within this many pixels on a side.
+const MAX_IMAGE_SIDE: i32 = 1000;
+
+// Colors positions by how many sensors cover them, sampling one position for
+// each pixel. Sensors are drawn in white and beacons in red.
+pub fn image(input: String, options: &ImageOptions) -> Image {
+    let sensors = super::shared::must_parse(parse, input.as_str())
+        .into_iter()
+        .map(|sensor| {
+            let distance = sensor.position.manhattan(sensor.closest_beacon);
+            (sensor, distance)
+        })
+        .collect::<Vec<_>>();
+    let bounds = BoundingBox::of(sensors.iter().flat_map(|(sensor, distance)| {
+        [
+            sensor.position - Vector::new(*distance, *distance),
+            sensor.position + Vector::new(*distance, *distance),
+        ]
+    }))
+    .unwrap_or_else(|| BoundingBox::new(Point::ORIGIN));
+
+    let longest_side = bounds.width().max(bounds.height());
+    let scale = (options.downsample as i32)
+        .max((longest_side + MAX_IMAGE_SIDE - 1) / MAX_IMAGE_SIDE)
+        .max(1);
+    let (width, height) = (
+        (bounds.width() + scale - 1) / scale,
+        (bounds.height() + scale - 1) / scale,
+    );
+    let pixel_of = |point: Point| -> Position {
+        let offset = point - bounds.min;
+        Point::new(offset.dx / scale, offset.dy / scale)
+    };
+
+    let coverage = Grid::from_cells(
+        width as usize,
+        (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(|(x, y)| {
+                let sample = bounds.min + Vector::new(x * scale + scale / 2, y * scale + scale / 2);
+                sensors
+                    .iter()
+                    .filter(|(sensor, distance)| sensor.position.manhattan(sample) <= *distance)
+                    .count()
+            })
+            .collect(),
+    );
+    let most_coverage = coverage.values().copied().max().unwrap_or(0);
+    let mut image = coverage.map(|count| {
+        options
+            .color_map
+            .scaled(*count as f64, 0.0, most_coverage as f64)
+    });
+    for (sensor, _) in &sensors {
+        for (point, color) in [
+            (sensor.position, Rgb::WHITE),
+            (sensor.closest_beacon, Rgb::RED),
+        ] {
+            if let Some(pixel) = pixel_of(point).to_unsigned() {
+                if let Some(cell) = image.get_mut(pixel) {
+                    *cell = color;
+                }
+            }
+        }
+    }
+    image
+}
+
+#[derive(Debug, Clone)]
+pub(super) struct Sensor {
+    position: Position,
+    closest_beacon: Position,
+}
+
+pub fn summarize(input: String) -> Summary {
+    let sensors = super::shared::must_parse(parse, input.as_str());
+    let bounds = BoundingBox::of(
+        sensors
+            .iter()
+            .flat_map(|sensor| [sensor.position, sensor.closest_beacon]),
+    )
+    .unwrap_or_else(|| BoundingBox::new(Point::ORIGIN));
+    Summary::new()
+        .field("sensors", sensors.len())
+        .field(
+            "beacons",
+            sensors
+                .iter()
+                .map(|sensor| sensor.closest_beacon)
+                .unique()
+                .count(),
+        )
+        .field("x range", format!("{}..={}", bounds.min.x, bounds.max.x))
+        .field("y range", format!("{}..={}", bounds.min.y, bounds.max.y))
+        .field(
+            "largest sensor range",
+            sensors
+                .iter()
+                .map(|sensor| sensor.position.manhattan(sensor.closest_beacon))
+                .max()
+                .unwrap_or(0),
+        )
+}
+
+pub fn dump(input: String) -> String {
+    format!("{:#?}", super::shared::must_parse(parse, input.as_str()))
+}
+
+pub(super) fn parse(input: &str) -> IResult<'_, Vec<Sensor>> {
+    lines(map(
+        tuple((
+            tag("Sensor at "),
+            point,
+            tag(": closest beacon is at "),
+            point,
+        )),
+        |(_, position, _, closest_beacon)| Sensor {
+            position,
+            closest_beacon,
+        },
+    ))(input)
+}
+
+fn point(input: &str) -> IResult<'_, Point> {
+    map(
+        separated_pair(key_value("x", signed), tag(", "), key_value("y", signed)),
+        Point::from,
+    )(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE_INPUT: &str = "Sensor at x=2, y=18: closest beacon is at x=-2, y=15
+Sensor at x=9, y=16: closest beacon is at x=10, y=16
+Sensor at x=13, y=2: closest beacon is at x=15, y=3
+Sensor at x=12, y=14: closest beacon is at x=10, y=16
+Sensor at x=10, y=20: closest beacon is at x=10, y=16
+Sensor at x=14, y=17: closest beacon is at x=10, y=16
+Sensor at x=8, y=7: closest beacon is at x=2, y=10
+Sensor at x=2, y=0: closest beacon is at x=2, y=10
+Sensor at x=0, y=11: closest beacon is at x=2, y=10
+Sensor at x=20, y=14: closest beacon is at x=25, y=17
+Sensor at x=17, y=20: closest beacon is at x=21, y=22
+Sensor at x=16, y=7: closest beacon is at x=15, y=3
+Sensor at x=14, y=3: closest beacon is at x=15, y=3
+Sensor at x=20, y=1: closest beacon is at x=15, y=3
+";
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(part1_solve(10, EXAMPLE_INPUT), 26)
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(part2_solve(20, EXAMPLE_INPUT), 56000011)
+    }
+
+    #[test]
+    fn test_summarize() {
+        assert_eq!(
+            summarize(EXAMPLE_INPUT.into()),
+            Summary::new()
+                .field("sensors", 14)
+                .field("beacons", 6)
+                .field("x range", "-2..=25")
+                .field("y range", "0..=22")
+                .field("largest sensor range", 10)
+        )
+    }
+}
@@ -0,0 +1,714 @@
+use std::{cmp::Reverse, collections::HashMap, fmt, fs};
+
+use itertools::Itertools;
+use nom::{
+    character::complete::{anychar, char},
+    sequence::separated_pair,
+};
+
+use super::{
+    params::Params,
+    shared::{
+        fail, must_parse,
+        parsing::{lines, IResult},
+    },
+};
+
+// In part 1, each round's second letter is the move to play.
+pub fn part1(input: String, params: &Params) -> u32 {
+    let game = load_game(params);
+    parse_rounds(&input)
+        .into_iter()
+        .map(|(opponent, player)| {
+            game.score(game.opponent_move(opponent), game.player_move(player))
+        })
+        .sum()
+}
+
+// In part 2, each round's second letter is the outcome to aim for instead.
+pub fn part2(input: String, params: &Params) -> u32 {
+    let game = load_game(params);
+    parse_rounds(&input)
+        .into_iter()
+        .map(|(opponent, letter)| {
+            let opponent = game.opponent_move(opponent);
+            let player = game.choose(opponent, game.outcome_for(letter));
+            game.score(opponent, player)
+        })
+        .sum()
+}
+
+// The `game` parameter picks one of the built-in games by name, or gives a
+// spec of its own, with `;` between lines. The `game_file` parameter reads a
+// spec from a file instead.
+fn load_game(params: &Params) -> Game {
+    let game_file: String = params.get("game_file", String::new());
+    let game: String = params.get("game", "rps".to_string());
+    let spec = if !game_file.is_empty() {
+        if params.iter().any(|(key, _)| key == "game") {
+            fail!("Only one of game and game_file can be given")
+        }
+        fs::read_to_string(&game_file)
+            .unwrap_or_else(|err| fail!("Could not read game file {}: {}", game_file, err))
+    } else {
+        match BUILT_IN_GAMES.iter().find(|(name, _)| *name == game) {
+            Some((_, spec)) => spec.to_string(),
+            None if game.contains(';') => game.replace(';', "\n"),
+            None => fail!(
+                "Unknown game {:?} (expected one of: {}, or a game spec)",
+                game,
+                BUILT_IN_GAMES.iter().map(|(name, _)| name).join(", ")
+            ),
+        }
+    };
+    Game::parse(&spec).unwrap_or_else(|err| fail!("Invalid game spec: {}", err))
+}
+
+// A game spec lists the moves, as
+//
+//     move <name> <score> <opponent letter> <player letter> beats <name>...
+//
+// and then the score for each outcome, with the letter that asks for it in
+// part 2:
+//
+//     outcome loss <score> <letter>
+//
+// Two moves where neither beats the other are a draw.
+const BUILT_IN_GAMES: &[(&str, &str)] = &[
+    (
+        "rps",
+        "\
+move rock 1 A X beats scissors
+move paper 2 B Y beats rock
+move scissors 3 C Z beats paper
+outcome loss 0 X
+outcome draw 3 Y
+outcome win 6 Z
+",
+    ),
+    (
+        "rpsls",
+        "\
+move rock 1 A X beats scissors lizard
+move paper 2 B Y beats rock spock
+move scissors 3 C Z beats paper lizard
+move lizard 4 D V beats paper spock
+move spock 5 E W beats rock scissors
+outcome loss 0 X
+outcome draw 3 Y
+outcome win 6 Z
+",
+    ),
+    (
+        "rps7",
+        "\
+move rock 1 A X beats fire scissors sponge
+move paper 2 B Y beats air water rock
+move scissors 3 C Z beats sponge paper air
+move fire 4 D S beats scissors sponge paper
+move sponge 5 E T beats paper air water
+move air 6 F U beats water rock fire
+move water 7 G V beats rock fire scissors
+outcome loss 0 X
+outcome draw 3 Y
+outcome win 6 Z
+",
+    ),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Outcome {
+    Loss,
+    Draw,
+    Win,
+}
+
+use Outcome::*;
+
+impl Outcome {
+    const ALL: [Outcome; 3] = [Loss, Draw, Win];
+
+    fn name(self) -> &'static str {
+        match self {
+            Loss => "loss",
+            Draw => "draw",
+            Win => "win",
+        }
+    }
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+struct Move {
+    name: String,
+    score: u32,
+    // The letters for this move in the opponent's and the player's columns.
+    opponent: char,
+    player: char,
+    // Indices of the moves this move beats.
+    beats: Vec<usize>,
+}
+
+// A game where each round, both players pick a move, and score points for the
+// move they picked and for the outcome. Moves are referred to by index.
+#[derive(Debug, PartialEq, Eq)]
+struct Game {
+    moves: Vec<Move>,
+    // Scores and part 2 letters for each outcome, indexed by Outcome.
+    outcomes: [(u32, char); 3],
+}
+
+impl Game {
+    fn parse(spec: &str) -> Result<Game, String> {
+        // Moves can beat moves defined after them, so names are resolved once
+        // every move is known.
+        let mut moves = Vec::new();
+        let mut beaten_names = Vec::new();
+        let mut outcomes = [None; 3];
+        for (i, line) in spec.lines().enumerate() {
+            let fail = |message: String| format!("line {}: {}", i + 1, message);
+            let words: Vec<&str> = line.split_whitespace().collect();
+            match words[..] {
+                [] => continue,
+                ["move", name, score, opponent, player, "beats", ref beaten @ ..] => {
+                    let score = score
+                        .parse()
+                        .map_err(|_| fail(format!("invalid score {:?}", score)))?;
+                    let (opponent, player) = (letter(opponent), letter(player));
+                    let (Some(opponent), Some(player)) = (opponent, player) else {
+                        return Err(fail("letters must be single characters".into()));
+                    };
+                    moves.push(Move {
+                        name: name.to_string(),
+                        score,
+                        opponent,
+                        player,
+                        beats: Vec::new(),
+                    });
+                    beaten_names.push((i, beaten.to_vec()));
+                }
+                ["outcome", name, score, letter_word] => {
+                    let Some(outcome) = Outcome::ALL.into_iter().find(|o| o.name() == name) else {
+                        return Err(fail(format!("unknown outcome {:?}", name)));
+                    };
+                    let score = score
+                        .parse()
+                        .map_err(|_| fail(format!("invalid score {:?}", score)))?;
+                    let Some(letter) = letter(letter_word) else {
+                        return Err(fail("letters must be single characters".into()));
+                    };
+                    outcomes[outcome as usize] = Some((score, letter));
+                }
+                _ => {
+                    return Err(fail(format!(
+                        "expected a move or an outcome, found {:?}",
+                        line
+                    )))
+                }
+            }
+        }
+
+        let indices: HashMap<&str, usize> = moves
+            .iter()
+            .enumerate()
+            .map(|(i, game_move)| (game_move.name.as_str(), i))
+            .collect();
+        let mut beats = Vec::new();
+        for (line, names) in &beaten_names {
+            let fail = |message: String| format!("line {}: {}", line + 1, message);
+            let beaten = names
+                .iter()
+                .map(|name| {
+                    indices
+                        .get(name)
+                        .copied()
+                        .ok_or_else(|| fail(format!("unknown move {:?}", name)))
+                })
+                .collect::<Result<Vec<usize>, String>>()?;
+            beats.push(beaten);
+        }
+        for (game_move, beaten) in moves.iter_mut().zip(beats) {
+            game_move.beats = beaten;
+        }
+
+        let [Some(loss), Some(draw), Some(win)] = outcomes else {
+            return Err("the scores for a loss, a draw and a win are all needed".into());
+        };
+        let game = Game {
+            moves,
+            outcomes: [loss, draw, win],
+        };
+        game.validate()?;
+        Ok(game)
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        if self.moves.is_empty() {
+            return Err("a game needs at least one move".into());
+        }
+        if self
+            .moves
+            .iter()
+            .map(|m| &m.name)
+            .duplicates()
+            .next()
+            .is_some()
+        {
+            return Err("move names must be unique".into());
+        }
+        for (column, letters) in [
+            (
+                "opponent",
+                self.moves.iter().map(|m| m.opponent).collect_vec(),
+            ),
+            ("player", self.moves.iter().map(|m| m.player).collect_vec()),
+            (
+                "outcome",
+                self.outcomes.iter().map(|(_, l)| *l).collect_vec(),
+            ),
+        ] {
+            if let Some(letter) = letters.into_iter().duplicates().next() {
+                return Err(format!("{} letter {} is used twice", column, letter));
+            }
+        }
+        for (i, game_move) in self.moves.iter().enumerate() {
+            for beaten in &game_move.beats {
+                if *beaten == i {
+                    return Err(format!("{} can't beat itself", game_move.name));
+                }
+                if self.moves[*beaten].beats.contains(&i) {
+                    return Err(format!(
+                        "{} and {} can't both beat each other",
+                        game_move.name, self.moves[*beaten].name
+                    ));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn outcome(&self, opponent: usize, player: usize) -> Outcome {
+        if self.moves[player].beats.contains(&opponent) {
+            Win
+        } else if self.moves[opponent].beats.contains(&player) {
+            Loss
+        } else {
+            Draw
+        }
+    }
+
+    fn score(&self, opponent: usize, player: usize) -> u32 {
+        let (outcome_score, _) = self.outcomes[self.outcome(opponent, player) as usize];
+        self.moves[player].score + outcome_score
+    }
+
+    // Picks a move with the given outcome against the opponent's move. When
+    // several moves would do, picks the one that scores the most.
+    fn choose(&self, opponent: usize, outcome: Outcome) -> usize {
+        self.try_choose(opponent, outcome).unwrap_or_else(|| {
+            fail!(
+                "No move gives a {} against {}",
+                outcome,
+                self.moves[opponent].name
+            )
+        })
+    }
+
+    // Like `choose`, but None when no move gives the outcome.
+    fn try_choose(&self, opponent: usize, outcome: Outcome) -> Option<usize> {
+        (0..self.moves.len())
+            .filter(|player| self.outcome(opponent, *player) == outcome)
+            .max_by_key(|player| (self.moves[*player].score, Reverse(*player)))
+    }
+
+    fn player_move(&self, letter: char) -> usize {
+        self.moves
+            .iter()
+            .position(|game_move| game_move.player == letter)
+            .unwrap_or_else(|| fail!("Invalid player move: {}", letter))
+    }
+
+    fn opponent_move(&self, letter: char) -> usize {
+        self.moves
+            .iter()
+            .position(|game_move| game_move.opponent == letter)
+            .unwrap_or_else(|| fail!("Invalid opponent move: {}", letter))
+    }
+
+    fn outcome_for(&self, letter: char) -> Outcome {
+        Outcome::ALL
+            .into_iter()
+            .find(|outcome| self.outcomes[*outcome as usize].1 == letter)
+            .unwrap_or_else(|| fail!("Invalid game outcome: {}", letter))
+    }
+}
+
+// Every way of reading the guide's second column, as a one-to-one map from
+// its letters to meanings (moves, or outcomes).
+struct CipherSearch<T> {
+    letters: Vec<char>,
+    meanings: Vec<T>,
+    // The score of each letter's rounds for each meaning it could have, or
+    // None if some round can't be played with that meaning (like an outcome
+    // that no move gives). Since each round's score only depends on its own
+    // letter, an interpretation's total is the sum of one of these per
+    // letter.
+    contributions: Vec<Vec<Option<u32>>>,
+    // Indices into `meanings` for each letter, with the total score, from the
+    // best interpretation to the worst, and then the unavailable ones.
+    interpretations: Vec<(Vec<usize>, Option<u32>)>,
+}
+
+impl<T: Copy + PartialEq> CipherSearch<T> {
+    fn new<F>(rounds: &[(usize, char)], letters: &[char], meanings: &[T], score: F) -> Self
+    where
+        F: Fn(usize, T) -> Option<u32>,
+    {
+        let contributions: Vec<Vec<Option<u32>>> = letters
+            .iter()
+            .map(|letter| {
+                meanings
+                    .iter()
+                    .map(|meaning| {
+                        rounds
+                            .iter()
+                            .filter(|(_, round_letter)| round_letter == letter)
+                            .map(|(opponent, _)| score(*opponent, *meaning))
+                            .sum()
+                    })
+                    .collect()
+            })
+            .collect();
+        let interpretations = (0..meanings.len())
+            .permutations(letters.len())
+            .map(|chosen| {
+                let total: Option<u32> = chosen
+                    .iter()
+                    .enumerate()
+                    .map(|(letter, meaning)| contributions[letter][*meaning])
+                    .sum();
+                (chosen, total)
+            })
+            .sorted_by_key(|(_, total)| (total.is_none(), Reverse(*total)))
+            .collect();
+        CipherSearch {
+            letters: letters.to_vec(),
+            meanings: meanings.to_vec(),
+            contributions,
+            interpretations,
+        }
+    }
+
+    // Lists every interpretation, marking the one that the given puzzle part
+    // uses, followed by the best and worst, and how much difference each
+    // letter's meaning makes to the total.
+    fn render(
+        &self,
+        title: &str,
+        part: (u32, Option<Vec<T>>),
+        name: impl Fn(T) -> String,
+    ) -> String {
+        let describe = |chosen: &[usize]| {
+            self.letters
+                .iter()
+                .zip(chosen)
+                .map(|(letter, meaning)| format!("{}={}", letter, name(self.meanings[*meaning])))
+                .join(" ")
+        };
+        let mut rendered = format!(
+            "{}: {} interpretations\n",
+            title,
+            self.interpretations.len()
+        );
+        if self.interpretations.is_empty() {
+            rendered.push_str(&format!(
+                "  none, with {} letters for {} meanings\n",
+                self.letters.len(),
+                self.meanings.len()
+            ));
+            return rendered;
+        }
+        let available = || {
+            self.interpretations
+                .iter()
+                .filter_map(|(chosen, total)| Some((chosen, (*total)?)))
+        };
+        let (part_number, part_meanings) = part;
+        for (chosen, total) in &self.interpretations {
+            let is_part = part_meanings.as_ref().is_some_and(|meanings| {
+                chosen
+                    .iter()
+                    .map(|meaning| self.meanings[*meaning])
+                    .eq(meanings.iter().copied())
+            });
+            rendered.push_str(&format!(
+                "  {}  {:>6}{}\n",
+                describe(chosen),
+                describe_total(*total),
+                if is_part {
+                    format!("  (part {})", part_number)
+                } else {
+                    String::new()
+                }
+            ));
+        }
+        if let (Some((best, best_total)), Some((worst, worst_total))) =
+            (available().next(), available().next_back())
+        {
+            rendered.push_str(&format!("best   {}  {}\n", describe(best), best_total));
+            rendered.push_str(&format!("worst  {}  {}\n", describe(worst), worst_total));
+        }
+        rendered.push_str("sensitivity\n");
+        for (letter, contributions) in self.letters.iter().zip(&self.contributions) {
+            let (min, max) = contributions
+                .iter()
+                .flatten()
+                .minmax()
+                .into_option()
+                .map_or((0, 0), |(min, max)| (*min, *max));
+            rendered.push_str(&format!(
+                "  {}  {:>6}  ({})\n",
+                letter,
+                max - min,
+                contributions
+                    .iter()
+                    .zip(&self.meanings)
+                    .map(|(score, meaning)| format!(
+                        "{} {}",
+                        name(*meaning),
+                        describe_total(*score)
+                    ))
+                    .join(", ")
+            ));
+        }
+        rendered
+    }
+}
+
+fn describe_total(total: Option<u32>) -> String {
+    total.map_or("unavailable".into(), |total| total.to_string())
+}
+
+// Scores the guide under every reading of its second column: as moves (like
+// part 1) and as outcomes (like part 2). A letter's sensitivity is how much
+// the total can change with what that letter means.
+pub fn report(input: String, params: &Params) -> String {
+    let game = load_game(params);
+    let rounds: Vec<(usize, char)> = parse_rounds(&input)
+        .into_iter()
+        .map(|(opponent, letter)| (game.opponent_move(opponent), letter))
+        .collect();
+    let letters: Vec<char> = rounds
+        .iter()
+        .map(|(_, letter)| *letter)
+        .unique()
+        .sorted()
+        .collect();
+
+    let moves: Vec<usize> = (0..game.moves.len()).collect();
+    let as_moves = CipherSearch::new(&rounds, &letters, &moves, |opponent, player| {
+        Some(game.score(opponent, player))
+    });
+    // Custom games can have outcomes that some moves can't be met with, which
+    // rules out the interpretations that would need them.
+    let as_outcomes = CipherSearch::new(&rounds, &letters, &Outcome::ALL, |opponent, outcome| {
+        Some(game.score(opponent, game.try_choose(opponent, outcome)?))
+    });
+
+    // The game's own letters, if they cover the guide.
+    let part1_moves = letters
+        .iter()
+        .map(|letter| game.moves.iter().position(|m| m.player == *letter))
+        .collect();
+    let part2_outcomes = letters
+        .iter()
+        .map(|letter| {
+            Outcome::ALL
+                .into_iter()
+                .find(|outcome| game.outcomes[*outcome as usize].1 == *letter)
+        })
+        .collect();
+    format!(
+        "{}\n{}",
+        as_moves.render("As moves", (1, part1_moves), |i| game.moves[i].name.clone()),
+        as_outcomes.render("As outcomes", (2, part2_outcomes), |outcome| outcome
+            .name()
+            .to_string())
+    )
+}
+
+fn letter(word: &str) -> Option<char> {
+    word.chars().exactly_one().ok()
+}
+
+// Each round is two letters: the opponent's move, and then the player's move
+// or outcome.
+fn parse_rounds(input: &str) -> Vec<(char, char)> {
+    must_parse(parse, input)
+}
+
+pub(super) fn parse(input: &str) -> IResult<'_, Vec<(char, char)>> {
+    lines(separated_pair(anychar, char(' '), anychar))(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+
+    use super::{super::shared::catch_failure, *};
+
+    const EXAMPLE_INPUT: &str = "A Y
+B X
+C Z
+";
+
+    fn with_game(game: &str) -> Params {
+        let mut params = Params::new();
+        params.insert("game", game);
+        params
+    }
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(part1(EXAMPLE_INPUT.into(), &Params::new()), 15)
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(part2(EXAMPLE_INPUT.into(), &Params::new()), 12)
+    }
+
+    #[test]
+    fn test_variants() {
+        // Lizard poisons Spock, and then Spock vaporizes rock.
+        let input = "E V\nA W\n";
+        assert_eq!(part1(input.into(), &with_game("rpsls")), (4 + 6) + (5 + 6));
+        // Against rock, both paper and Spock win, and Spock scores more.
+        assert_eq!(part2("A Z\n".into(), &with_game("rpsls")), 5 + 6);
+        // Every move in a balanced game loses to as many moves as it beats.
+        let game = load_game(&with_game("rps7"));
+        for opponent in 0..game.moves.len() {
+            let wins = (0..game.moves.len())
+                .filter(|player| game.outcome(opponent, *player) == Win)
+                .count();
+            assert_eq!(wins, 3);
+        }
+    }
+
+    #[test]
+    fn test_game_file() {
+        let rps7 = BUILT_IN_GAMES
+            .iter()
+            .find(|(name, _)| *name == "rps7")
+            .unwrap()
+            .1;
+        let path = env::temp_dir().join(format!("advent-day2-test-{}.txt", std::process::id()));
+        fs::write(&path, rps7).unwrap();
+        let mut params = Params::new();
+        params.insert("game_file", path.to_str().unwrap());
+        let input = "A X\nB X\nC X\n";
+        let from_file = (part1(input.into(), &params), part2(input.into(), &params));
+        params.insert("game", "rps7");
+        let both = catch_failure(|| load_game(&params));
+        fs::remove_file(&path).unwrap();
+
+        let built_in = with_game("rps7");
+        assert_eq!(
+            from_file,
+            (
+                part1(input.into(), &built_in),
+                part2(input.into(), &built_in)
+            )
+        );
+        assert_eq!(
+            both.err(),
+            Some("Only one of game and game_file can be given".into())
+        );
+        let mut missing = Params::new();
+        missing.insert("game_file", path.to_str().unwrap());
+        assert!(catch_failure(|| load_game(&missing))
+            .err()
+            .unwrap()
+            .starts_with("Could not read game file "));
+    }
+
+    #[test]
+    fn test_custom_game() {
+        // A coin toss, where heads beats tails.
+        let game = "move heads 1 H X beats tails; move tails 2 T Y beats; \
+                    outcome loss 0 L; outcome draw 1 D; outcome win 5 W";
+        assert_eq!(part1("T X\nH X\n".into(), &with_game(game)), 6 + 2);
+        assert_eq!(part2("H D\nT W\n".into(), &with_game(game)), 2 + 6);
+        assert_eq!(
+            catch_failure(|| part2("H W\n".into(), &with_game(game))),
+            Err("No move gives a win against heads".into())
+        );
+        // The report rules out readings that need such an outcome, rather
+        // than failing.
+        let report = report("H W\nT L\n".into(), &with_game(game));
+        assert!(report.contains("  L=loss W=win  unavailable  (part 2)\n"));
+        assert!(report.contains("worst  L=draw W=loss  5\n"));
+        assert!(report.contains("  W       0  (loss 2, draw 2, win unavailable)\n"));
+    }
+
+    #[test]
+    fn test_report() {
+        assert_eq!(
+            report(EXAMPLE_INPUT.into(), &Params::new()),
+            "\
+As moves: 6 interpretations
+  X=scissors Y=paper Z=rock      24
+  X=rock Y=paper Z=scissors      15  (part 1)
+  X=paper Y=rock Z=scissors      15
+  X=paper Y=scissors Z=rock      15
+  X=scissors Y=rock Z=paper      15
+  X=rock Y=scissors Z=paper       6
+best   X=scissors Y=paper Z=rock  24
+worst  X=rock Y=scissors Z=paper  6
+sensitivity
+  X       8  (rock 1, paper 5, scissors 9)
+  Y       5  (rock 4, paper 8, scissors 3)
+  Z       5  (rock 7, paper 2, scissors 6)
+
+As outcomes: 6 interpretations
+  X=win Y=loss Z=draw      18
+  X=loss Y=win Z=draw      15
+  X=draw Y=loss Z=win      15
+  X=draw Y=win Z=loss      15
+  X=win Y=draw Z=loss      15
+  X=loss Y=draw Z=win      12  (part 2)
+best   X=win Y=loss Z=draw  18
+worst  X=loss Y=draw Z=win  12
+sensitivity
+  X       8  (loss 1, draw 5, win 9)
+  Y       5  (loss 3, draw 4, win 8)
+  Z       5  (loss 2, draw 6, win 7)
+"
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            Game::parse("move rock 1 A X beats paper\n"),
+            Err("line 1: unknown move \"paper\"".into())
+        );
+        assert_eq!(
+            Game::parse("move rock 1 A X beats\noutcome tie 3 Y\n"),
+            Err("line 2: unknown outcome \"tie\"".into())
+        );
+        assert_eq!(
+            Game::parse(
+                "move a 1 A X beats b\nmove b 1 B X beats\n\
+                 outcome loss 0 X\noutcome draw 3 Y\noutcome win 6 Z\n"
+            ),
+            Err("player letter X is used twice".into())
+        );
+        assert!(Game::parse("move a 1 A X beats\n").is_err());
+    }
+}
@@ -0,0 +1,425 @@
+use std::ops::{BitAnd, BitOr};
+
+use itertools::Itertools;
+
+use super::{inspect::Summary, params::Params, shared::fail};
+
+// Each rucksack's compartments share exactly one item type. Intersecting the
+// compartments' item sets finds it.
+pub fn part1(input: String) -> u32 {
+    parse(&input)
+        .iter()
+        .enumerate()
+        .map(|(i, rucksack)| {
+            let Some((first, second)) = rucksack.compartments else {
+                fail!("Rucksack {} has an odd number of items", i + 1)
+            };
+            (first & second)
+                .priorities()
+                .exactly_one()
+                .unwrap_or_else(|shared| {
+                    fail!(
+                        "Rucksack {} did not contain exactly one shared item: found {}",
+                        i + 1,
+                        shared.count()
+                    )
+                })
+        })
+        .sum()
+}
+
+// Each group's badge is the one item type that all of its rucksacks have.
+//
+// Groups are three rucksacks each by default. The `group_size` parameter
+// changes that, and the `sliding` parameter makes every run of that many
+// consecutive rucksacks a group, rather than splitting the rucksacks into
+// separate groups.
+pub fn part2(input: String, params: &Params) -> u32 {
+    let group_size = group_size(params);
+    let rucksacks: Vec<Items> = parse(&input)
+        .iter()
+        .map(|rucksack| rucksack.items)
+        .collect();
+    groups(&rucksacks, group_size, params)
+        .into_iter()
+        .enumerate()
+        .map(|(i, (_, group))| {
+            if group.len() != group_size {
+                fail!(
+                    "Group {} has {} rucksacks instead of {}",
+                    i + 1,
+                    group.len(),
+                    group_size
+                )
+            }
+            let common = group
+                .iter()
+                .fold(Items::ALL, |common, items| common & *items);
+            common.priorities().exactly_one().unwrap_or_else(|badges| {
+                fail!(
+                    "Group {} did not contain exactly one badge: found {}",
+                    i + 1,
+                    badges.count()
+                )
+            })
+        })
+        .sum()
+}
+
+#[derive(Debug)]
+pub(super) struct Rucksack {
+    items: Items,
+    // None if the rucksack has an odd number of items, and so can't be split
+    // into compartments.
+    compartments: Option<(Items, Items)>,
+}
+
+// Each line is a rucksack's items, with the first half in one compartment
+// and the second half in the other.
+pub(super) fn parse(input: &str) -> Vec<Rucksack> {
+    input
+        .lines()
+        .map(|rucksack| match compartments(rucksack) {
+            Some((first, second)) => {
+                let (first, second) = (Items::of(first), Items::of(second));
+                Rucksack {
+                    items: first | second,
+                    compartments: Some((first, second)),
+                }
+            }
+            None => Rucksack {
+                items: Items::of(rucksack),
+                compartments: None,
+            },
+        })
+        .collect()
+}
+
+// Splits a rucksack's items into its two compartments, unless it has an odd
+// number of items.
+fn compartments(rucksack: &str) -> Option<(&str, &str)> {
+    let items = rucksack.chars().count();
+    if !items.is_multiple_of(2) {
+        return None;
+    }
+    let middle = rucksack
+        .char_indices()
+        .nth(items / 2)
+        .map_or(rucksack.len(), |(i, _)| i);
+    Some(rucksack.split_at(middle))
+}
+
+fn group_size(params: &Params) -> usize {
+    let group_size = params.get("group_size", 3);
+    if group_size == 0 {
+        fail!("Groups must have at least one rucksack")
+    }
+    group_size
+}
+
+// Splits the rucksacks into groups, as (index of first rucksack, rucksacks)
+// pairs. Without sliding groups, the last group may be short.
+fn groups<'a, T>(rucksacks: &'a [T], group_size: usize, params: &Params) -> Vec<(usize, &'a [T])> {
+    if params.get("sliding", false) {
+        rucksacks.windows(group_size).enumerate().collect()
+    } else {
+        rucksacks
+            .chunks(group_size)
+            .enumerate()
+            .map(|(i, group)| (i * group_size, group))
+            .collect()
+    }
+}
+
+// Audits every rucksack and group, listing each problem with its line number
+// rather than failing at the first one: rucksacks with an odd number of
+// items, invalid items, rucksacks whose compartments don't share exactly one
+// item type, and groups without exactly one badge. For inputs without
+// problems, the report also gives the answers.
+pub fn report(input: String, params: &Params) -> String {
+    let group_size = group_size(params);
+    let mut problems = Vec::new();
+    let (mut odd, mut invalid, mut unshared, mut badgeless) = (0, 0, 0, 0);
+
+    let mut rucksacks = Vec::new();
+    let mut part1 = 0;
+    for (i, rucksack) in input.lines().enumerate() {
+        let line = i + 1;
+        let (items, invalid_items) = Items::parse(rucksack);
+        for (column, item) in &invalid_items {
+            problems.push(format!(
+                "line {}: invalid item {:?} at column {}",
+                line, item, column
+            ));
+        }
+        invalid += invalid_items.len();
+        rucksacks.push(items);
+
+        let Some((first, second)) = compartments(rucksack) else {
+            odd += 1;
+            problems.push(format!(
+                "line {}: odd number of items ({}), so the compartments can't be split evenly",
+                line,
+                rucksack.chars().count()
+            ));
+            continue;
+        };
+        let shared = Items::parse(first).0 & Items::parse(second).0;
+        part1 += shared.priorities().sum::<u32>();
+        if shared.priorities().count() != 1 {
+            unshared += 1;
+            problems.push(format!(
+                "line {}: compartments share {}",
+                line,
+                shared.describe()
+            ));
+        }
+    }
+
+    let mut part2 = 0;
+    let groups = groups(&rucksacks, group_size, params);
+    for (i, (start, group)) in groups.iter().enumerate() {
+        let lines = format!(
+            "group {} (lines {}-{})",
+            i + 1,
+            start + 1,
+            start + group.len()
+        );
+        if group.len() != group_size {
+            badgeless += 1;
+            problems.push(format!(
+                "{}: only {} of {} rucksacks",
+                lines,
+                group.len(),
+                group_size
+            ));
+            continue;
+        }
+        let common = group
+            .iter()
+            .fold(Items::ALL, |common, items| common & *items);
+        part2 += common.priorities().sum::<u32>();
+        if common.priorities().count() != 1 {
+            badgeless += 1;
+            problems.push(format!(
+                "{}: rucksacks have {} in common",
+                lines,
+                common.describe()
+            ));
+        }
+    }
+
+    let answer = |answer: u32, ok: bool| {
+        if ok {
+            answer.to_string()
+        } else {
+            "-".to_string()
+        }
+    };
+    let summary = Summary::new()
+        .field("rucksacks", rucksacks.len())
+        .field("groups", groups.len())
+        .field("odd-length rucksacks", odd)
+        .field("invalid items", invalid)
+        .field("without one shared item", unshared)
+        .field("groups without one badge", badgeless)
+        .field("part 1", answer(part1, odd + invalid + unshared == 0))
+        .field("part 2", answer(part2, invalid + badgeless == 0));
+    if problems.is_empty() {
+        summary.to_string()
+    } else {
+        format!("{}\n{}\n", summary, problems.join("\n"))
+    }
+}
+
+// A set of item types, as a bitmask with bit `p` set for the item with
+// priority `p`. Priorities run from 1 to 52, so bit 0 is never set.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Items(u64);
+
+impl Items {
+    const ALL: Items = Items(((1 << 52) - 1) << 1);
+
+    fn of(items: &str) -> Items {
+        Items(
+            items
+                .chars()
+                .fold(0, |mask, item| mask | 1 << item_priority(item)),
+        )
+    }
+
+    // Like `of`, but leaves out invalid items instead of failing, and returns
+    // them with their 1-indexed columns.
+    fn parse(items: &str) -> (Items, Vec<(usize, char)>) {
+        let mut mask = 0;
+        let mut invalid = Vec::new();
+        for (i, item) in items.chars().enumerate() {
+            match priority(item) {
+                Some(priority) => mask |= 1 << priority,
+                None => invalid.push((i + 1, item)),
+            }
+        }
+        (Items(mask), invalid)
+    }
+
+    // Like "no item types", or "2 item types: a, B".
+    fn describe(self) -> String {
+        let items = self.priorities().map(item).collect::<Vec<char>>();
+        match items.len() {
+            0 => "no item types".to_string(),
+            1 => format!("1 item type: {}", items[0]),
+            n => format!("{} item types: {}", n, items.iter().join(", ")),
+        }
+    }
+
+    // The priorities of the items in the set, from lowest to highest.
+    fn priorities(self) -> impl Iterator<Item = u32> {
+        (1..=52).filter(move |priority| self.0 & 1 << priority != 0)
+    }
+}
+
+impl BitAnd for Items {
+    type Output = Items;
+
+    fn bitand(self, rhs: Items) -> Items {
+        Items(self.0 & rhs.0)
+    }
+}
+
+impl BitOr for Items {
+    type Output = Items;
+
+    fn bitor(self, rhs: Items) -> Items {
+        Items(self.0 | rhs.0)
+    }
+}
+
+fn item_priority(item: char) -> u32 {
+    priority(item).unwrap_or_else(|| fail!("Invalid rucksack item detected: {}", item))
+}
+
+fn priority(item: char) -> Option<u32> {
+    if item.is_ascii_lowercase() {
+        Some((item as u32) - 96)
+    } else if item.is_ascii_uppercase() {
+        Some((item as u32) - 38)
+    } else {
+        None
+    }
+}
+
+fn item(priority: u32) -> char {
+    match priority {
+        1..=26 => (b'a' + priority as u8 - 1) as char,
+        _ => (b'A' + priority as u8 - 27) as char,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{super::shared::catch_failure, *};
+
+    const EXAMPLE_INPUT: &str = "vJrwpWtwJgWrhcsFMMfFFhFp
+jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL
+PmmdzqPrVvPwwTWBwg
+wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn
+ttgJtRGJQctTZtZT
+CrZsJsPPZsGzwwsLwLmpwMDw
+";
+
+    fn with_params(params: &[(&str, &str)]) -> Params {
+        let mut with = Params::new();
+        for (key, value) in params {
+            with.insert(key, value);
+        }
+        with
+    }
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(part1(EXAMPLE_INPUT.into()), 157);
+        assert_eq!(
+            catch_failure(|| part1("aa\nab\n".into())),
+            Err("Rucksack 2 did not contain exactly one shared item: found 0".into())
+        );
+        assert_eq!(
+            catch_failure(|| part1("abab\n".into())),
+            Err("Rucksack 1 did not contain exactly one shared item: found 2".into())
+        );
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(part2(EXAMPLE_INPUT.into(), &Params::new()), 70)
+    }
+
+    #[test]
+    fn test_group_sizes() {
+        // Every other rucksack shares one item type with its neighbor.
+        let input = "ab\nbc\ncd\nde\n";
+        let pairs = with_params(&[("group_size", "2")]);
+        assert_eq!(part2(input.into(), &pairs), 2 + 4);
+        let sliding = with_params(&[("group_size", "2"), ("sliding", "true")]);
+        assert_eq!(part2(input.into(), &sliding), 2 + 3 + 4);
+        assert_eq!(
+            catch_failure(|| part2("ab\nbc\nbd\nbe\n".into(), &Params::new())),
+            Err("Group 2 has 1 rucksacks instead of 3".into())
+        );
+        assert_eq!(
+            catch_failure(|| part2(input.into(), &with_params(&[("group_size", "4")]))),
+            Err("Group 1 did not contain exactly one badge: found 0".into())
+        );
+    }
+
+    #[test]
+    fn test_report() {
+        assert_eq!(
+            report(EXAMPLE_INPUT.into(), &Params::new()),
+            "\
+rucksacks                 6
+groups                    2
+odd-length rucksacks      0
+invalid items             0
+without one shared item   0
+groups without one badge  0
+part 1                    157
+part 2                    70
+"
+        );
+        let input = "abcb\naBcaB\nxy1bx\nbBbB\n";
+        assert_eq!(
+            catch_failure(|| part1("aébc\n".into())),
+            Err("Invalid rucksack item detected: é".into())
+        );
+        assert_eq!(
+            report(input.into(), &Params::new()),
+            "\
+rucksacks                 4
+groups                    2
+odd-length rucksacks      2
+invalid items             1
+without one shared item   1
+groups without one badge  2
+part 1                    -
+part 2                    -
+
+line 2: odd number of items (5), so the compartments can't be split evenly
+line 3: invalid item '1' at column 3
+line 3: odd number of items (5), so the compartments can't be split evenly
+line 4: compartments share 2 item types: b, B
+group 1 (lines 1-3): rucksacks have no item types in common
+group 2 (lines 4-4): only 1 of 3 rucksacks
+"
+        );
+    }
+
+    #[test]
+    fn test_items() {
+        assert_eq!(Items::of("aZz").priorities().collect_vec(), vec![1, 26, 52]);
+        assert_eq!(Items::ALL.priorities().count(), 52);
+        assert_eq!(
+            catch_failure(|| Items::of("a1")),
+            Err("Invalid rucksack item detected: 1".into())
+        );
+    }
+}
@@ -0,0 +1,615 @@
+use std::{
+    cmp::Reverse,
+    collections::BTreeSet,
+    fmt::{self, Display, Formatter},
+};
+
+use itertools::Itertools;
+use nom::{character::complete::char, combinator::map, sequence::separated_pair};
+
+use super::{
+    inspect::Summary,
+    params::Params,
+    shared::{
+        fail,
+        parsing::{lines, unsigned, IResult},
+        range_set::RangeSet,
+    },
+};
+
+// Each assignment is a single range of sections, but keeping it as a set
+// makes containment and overlap checks into set operations.
+type Section = RangeSet<u32>;
+
+pub fn part1(input: String) -> usize {
+    count_pairs(
+        |(a, b)| -> bool { a.is_superset(b) || b.is_superset(a) },
+        input.as_str(),
+    )
+}
+
+pub fn part2(input: String) -> usize {
+    count_pairs(overlaps, input.as_str())
+}
+
+fn count_pairs<F>(predicate: F, input: &str) -> usize
+where
+    F: Fn(&&(Section, Section)) -> bool,
+{
+    super::shared::must_parse(parse, input)
+        .iter()
+        .filter(predicate)
+        .count()
+}
+
+fn overlaps(input: &&(Section, Section)) -> bool {
+    let (a, b) = input;
+    !a.intersection(b).is_empty()
+}
+
+pub fn summarize(input: String) -> Summary {
+    let pairs = super::shared::must_parse(parse, input.as_str());
+    let sections = pairs.iter().flat_map(|(a, b)| [a, b]);
+    Summary::new()
+        .field("pairs", pairs.len())
+        .field(
+            "lowest section",
+            sections.clone().filter_map(Section::min).min().unwrap_or(0),
+        )
+        .field(
+            "highest section",
+            sections.clone().filter_map(Section::max).max().unwrap_or(0),
+        )
+        .field(
+            "widest assignment",
+            sections.map(Section::len).max().unwrap_or(0),
+        )
+}
+
+// One elf's assignment, for comparing assignments across the whole roster.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Assignment {
+    start: u32,
+    end: u32,
+    // The 1-indexed line the assignment is on, and whether it's the first or
+    // second elf on that line.
+    line: usize,
+    second: bool,
+}
+
+impl Display for Assignment {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}-{} (line {}, {} elf)",
+            self.start,
+            self.end,
+            self.line,
+            if self.second { "second" } else { "first" }
+        )
+    }
+}
+
+type Pair = (Assignment, Assignment);
+
+// Every elf's assignment, sorted by start, and then from the longest to the
+// shortest, so that an assignment can only contain assignments after it.
+// Empty assignments (like 5-4) have no sections to compare, and are left out.
+struct Roster {
+    assignments: Vec<Assignment>,
+}
+
+impl Roster {
+    fn new(pairs: &[(Section, Section)]) -> Roster {
+        let assignments = pairs
+            .iter()
+            .enumerate()
+            .flat_map(|(i, (a, b))| [(i, false, a), (i, true, b)])
+            .filter_map(|(i, second, section)| {
+                Some(Assignment {
+                    start: section.min()?,
+                    end: section.max()?,
+                    line: i + 1,
+                    second,
+                })
+            })
+            .sorted_by_key(|a| (a.start, Reverse(a.end), a.line, a.second))
+            .collect();
+        Roster { assignments }
+    }
+
+    // Counts overlapping pairs without listing them. In sorted order, an
+    // assignment overlaps every earlier assignment except those that end
+    // before it starts, and every assignment that ends before it starts is
+    // earlier.
+    fn count_overlapping(&self) -> u64 {
+        let ends: Vec<u32> = self.assignments.iter().map(|a| a.end).sorted().collect();
+        self.assignments
+            .iter()
+            .enumerate()
+            .map(|(i, a)| (i - ends.partition_point(|end| *end < a.start)) as u64)
+            .sum()
+    }
+
+    // Counts containing pairs without listing them. In sorted order, an
+    // assignment is contained by the earlier assignments that end no earlier
+    // than it, which a Fenwick tree over the ends counts in O(log n).
+    fn count_containing(&self) -> u64 {
+        let ends: Vec<u32> = self
+            .assignments
+            .iter()
+            .map(|a| a.end)
+            .sorted()
+            .dedup()
+            .collect();
+        let mut tree = vec![0u64; ends.len() + 1];
+        let mut contained = 0;
+        for (i, a) in self.assignments.iter().enumerate() {
+            // The number of earlier assignments ending before this one does.
+            let rank = ends.partition_point(|end| *end < a.end);
+            let mut position = rank;
+            let mut ending_before = 0;
+            while position > 0 {
+                ending_before += tree[position];
+                position &= position - 1;
+            }
+            contained += i as u64 - ending_before;
+
+            let mut position = rank + 1;
+            while position < tree.len() {
+                tree[position] += 1;
+                position += position & position.wrapping_neg();
+            }
+        }
+        contained
+    }
+
+    // Lists up to `limit` overlapping pairs and up to `limit` containing pairs,
+    // by sweeping over the assignments in order while keeping the ones that
+    // haven't ended yet in a set ordered by end. Each new assignment overlaps
+    // everything in the set, and is contained by everything in the set that
+    // ends no earlier than it. That makes this O(n log n) plus the number of
+    // pairs listed.
+    fn list_pairs(&self, limit: usize) -> (Vec<Pair>, Vec<Pair>) {
+        let mut overlapping = Vec::new();
+        let mut containing = Vec::new();
+        let mut active: BTreeSet<(u32, usize)> = BTreeSet::new();
+        for (i, a) in self.assignments.iter().enumerate() {
+            if overlapping.len() >= limit && containing.len() >= limit {
+                break;
+            }
+            while active.first().is_some_and(|(end, _)| *end < a.start) {
+                active.pop_first();
+            }
+            let room = limit.saturating_sub(overlapping.len());
+            overlapping.extend(
+                active
+                    .iter()
+                    .take(room)
+                    .map(|(_, j)| (self.assignments[*j], *a)),
+            );
+            let room = limit.saturating_sub(containing.len());
+            containing.extend(
+                active
+                    .range((a.end, 0)..)
+                    .take(room)
+                    .map(|(_, j)| (self.assignments[*j], *a)),
+            );
+            active.insert((a.end, i));
+        }
+        (overlapping, containing)
+    }
+
+    // The most elves assigned to any one section, and the sections that have
+    // that many.
+    fn busiest(&self) -> (usize, RangeSet<u32>) {
+        // Assignments end after their last section, so that touching
+        // assignments don't overlap.
+        let events = self
+            .assignments
+            .iter()
+            .flat_map(|a| [(a.start as u64, 1), (a.end as u64 + 1, -1)])
+            .sorted()
+            .collect_vec();
+        let mut elves: i64 = 0;
+        let mut most = 0;
+        let mut busiest = Vec::new();
+        for (i, (position, change)) in events.iter().enumerate() {
+            elves += change;
+            match events.get(i + 1) {
+                // Wait until every change at this position is counted.
+                Some((next, _)) if next == position => continue,
+                Some((next, _)) => {
+                    let span = (*position as u32)..=(*next - 1) as u32;
+                    if elves as usize > most {
+                        most = elves as usize;
+                        busiest = vec![span];
+                    } else if elves as usize == most && most > 0 {
+                        busiest.push(span);
+                    }
+                }
+                None => {}
+            }
+        }
+        (most, busiest.into_iter().collect())
+    }
+
+    fn assigned(&self) -> RangeSet<u32> {
+        self.assignments.iter().map(|a| a.start..=a.end).collect()
+    }
+
+    // The most assignments that can be kept without any two overlapping, by
+    // the usual interval scheduling greedy: going by end, keep every
+    // assignment that starts after the last kept one ends. Returns what
+    // becomes of each assignment, in roster order.
+    fn drop_plan(&self) -> Vec<Option<Assignment>> {
+        let mut plan = vec![None; self.assignments.len()];
+        let mut last_end = None;
+        for i in (0..self.assignments.len()).sorted_by_key(|i| self.assignments[*i].end) {
+            let a = self.assignments[i];
+            if last_end.is_none_or(|end| a.start > end) {
+                plan[i] = Some(a);
+                last_end = Some(a.end);
+            }
+        }
+        plan
+    }
+
+    // Changes as few assignments as possible, like `drop_plan`, whose
+    // assignments it keeps as they are, but shrinks the assignments that plan
+    // drops into whatever sections are still free, rather than dropping them.
+    // Each takes the longest free run of its own sections, going by start.
+    // Assignments with no free sections are still dropped.
+    fn shrink_plan(&self) -> Vec<Option<Assignment>> {
+        let mut plan = self.drop_plan();
+        let mut covered: RangeSet<u32> = plan.iter().flatten().map(|a| a.start..=a.end).collect();
+        for i in (0..self.assignments.len())
+            .filter(|i| plan[*i].is_none())
+            .sorted_by_key(|i| {
+                let a = self.assignments[*i];
+                (a.start, a.end)
+            })
+        {
+            let a = self.assignments[i];
+            let free = RangeSet::from_iter([a.start..=a.end]).difference(&covered);
+            if let Some(run) = free
+                .iter()
+                .max_by_key(|run| (run.end() - run.start(), Reverse(*run.start())))
+            {
+                plan[i] = Some(Assignment {
+                    start: *run.start(),
+                    end: *run.end(),
+                    ..a
+                });
+                covered.insert(run);
+            }
+        }
+        plan
+    }
+}
+
+// Describes a plan from `drop_plan` or `shrink_plan`, with the roster it
+// leaves in section order.
+fn describe_plan(name: &str, roster: &Roster, plan: &[Option<Assignment>]) -> String {
+    let changes = roster.assignments.iter().zip(plan);
+    let shrunk = changes
+        .clone()
+        .filter(|(a, kept)| kept.is_some_and(|kept| kept != **a))
+        .count();
+    let dropped = changes.clone().filter(|(_, kept)| kept.is_none()).count();
+    // Only sections that no kept elf still covers need a new elf.
+    let kept: RangeSet<u32> = plan.iter().flatten().map(|a| a.start..=a.end).collect();
+    let reassigned = roster.assigned().difference(&kept).len();
+    let summary = Summary::new()
+        .field("plan", name)
+        .field("elves kept", plan.len() - dropped)
+        .field("elves shrunk", shrunk)
+        .field("elves dropped", dropped)
+        .field("sections reassigned", reassigned);
+
+    let mut report = format!("\n{}\nConflict-free roster\n", summary);
+    for (a, kept) in changes
+        .clone()
+        .filter_map(|(a, kept)| Some((a, (*kept)?)))
+        .sorted_by_key(|(_, kept)| kept.start)
+    {
+        report.push_str(&format!("  {}", kept));
+        if kept != *a {
+            report.push_str(&format!(", shrunk from {}-{}", a.start, a.end));
+        }
+        report.push('\n');
+    }
+    if dropped > 0 {
+        report.push_str("\nDropped\n");
+        for (a, _) in changes.filter(|(_, kept)| kept.is_none()) {
+            report.push_str(&format!("  {}\n", a));
+        }
+    }
+    report
+}
+
+fn describe_sections(sections: &RangeSet<u32>) -> String {
+    if sections.is_empty() {
+        return "none".into();
+    }
+    sections
+        .iter()
+        .map(|range| {
+            let (start, end) = range.into_inner();
+            if start == end {
+                start.to_string()
+            } else {
+                format!("{}-{}", start, end)
+            }
+        })
+        .join(", ")
+}
+
+// Compares every elf's assignment with every other elf's, across all lines
+// rather than only within each pair. The `list` parameter limits how many of
+// the overlapping and containing pairs are listed; there can be too many to
+// list for big rosters. The `plan` parameter adds a plan for getting rid of
+// every overlap by changing as few elves as possible, either by dropping the
+// rest (`plan=drop`) or by shrinking them into sections nobody else kept
+// (`plan=shrink`).
+pub fn report(input: String, params: &Params) -> String {
+    let pairs = super::shared::must_parse(parse, input.as_str());
+    let limit = params.get("list", 10);
+    let roster = Roster::new(&pairs);
+    let plan = match params.get("plan", String::new()).as_str() {
+        "" => None,
+        "drop" => Some(("drop", roster.drop_plan())),
+        "shrink" => Some(("shrink", roster.shrink_plan())),
+        other => fail!(
+            "Invalid value for parameter plan: {:?}: expected drop or shrink",
+            other
+        ),
+    };
+    let (most, busiest) = roster.busiest();
+    let (overlapping, containing) = roster.list_pairs(limit);
+    let (overlapping_count, containing_count) =
+        (roster.count_overlapping(), roster.count_containing());
+
+    let summary = Summary::new()
+        .field("elves", roster.assignments.len())
+        .field("overlapping pairs", overlapping_count)
+        .field("containing pairs", containing_count)
+        .field("most elves per section", most)
+        .field("busiest sections", describe_sections(&busiest))
+        .field(
+            "unassigned sections",
+            describe_sections(&roster.assigned().gaps()),
+        );
+    let mut report = summary.to_string();
+    for (title, pairs, count, relation) in [
+        (
+            "Overlapping pairs",
+            overlapping,
+            overlapping_count,
+            "overlaps",
+        ),
+        ("Containing pairs", containing, containing_count, "contains"),
+    ] {
+        if pairs.is_empty() {
+            continue;
+        }
+        report.push_str(&format!("\n{}", title));
+        if (pairs.len() as u64) < count {
+            report.push_str(&format!(" (first {} of {})", pairs.len(), count));
+        }
+        report.push('\n');
+        for (a, b) in pairs {
+            report.push_str(&format!("  {} {} {}\n", a, relation, b));
+        }
+    }
+    if let Some((name, plan)) = plan {
+        report.push_str(&describe_plan(name, &roster, &plan));
+    }
+    report
+}
+
+pub fn dump(input: String) -> String {
+    format!("{:#?}", super::shared::must_parse(parse, input.as_str()))
+}
+
+pub(super) fn parse(input: &str) -> IResult<'_, Vec<(Section, Section)>> {
+    lines(pair)(input)
+}
+
+fn pair(input: &str) -> IResult<'_, (Section, Section)> {
+    separated_pair(section, char(','), section)(input)
+}
+
+fn section(input: &str) -> IResult<'_, Section> {
+    map(
+        separated_pair(unsigned, char('-'), unsigned),
+        |(start, end)| Section::from(start..=end),
+    )(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE_INPUT: &str = "2-4,6-8
+2-3,4-5
+5-7,7-9
+2-8,3-7
+6-6,4-6
+2-6,4-8
+";
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(part1(EXAMPLE_INPUT.into()), 2)
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(part2(EXAMPLE_INPUT.into()), 4)
+    }
+
+    #[test]
+    fn test_report() {
+        let mut params = Params::new();
+        params.insert("list", "3");
+        assert_eq!(
+            report(EXAMPLE_INPUT.into(), &params),
+            "\
+elves                   12
+overlapping pairs       49
+containing pairs        29
+most elves per section  8
+busiest sections        6
+unassigned sections     none
+
+Overlapping pairs (first 3 of 49)
+  2-8 (line 4, first elf) overlaps 2-6 (line 6, first elf)
+  2-6 (line 6, first elf) overlaps 2-4 (line 1, first elf)
+  2-8 (line 4, first elf) overlaps 2-4 (line 1, first elf)
+
+Containing pairs (first 3 of 29)
+  2-8 (line 4, first elf) contains 2-6 (line 6, first elf)
+  2-6 (line 6, first elf) contains 2-4 (line 1, first elf)
+  2-8 (line 4, first elf) contains 2-4 (line 1, first elf)
+"
+        );
+        // Sections 4 to 9 are nobody's.
+        assert!(report("1-3,2-3\n10-10,1-1\n".into(), &params).contains(
+            "most elves per section  2\n\
+             busiest sections        1-3\n\
+             unassigned sections     4-9\n"
+        ));
+    }
+
+    #[test]
+    fn test_roster_counts() {
+        // Check the sweep against comparing every pair, on a roster with lots
+        // of shared starts, shared ends and identical assignments.
+        let input = (0..200)
+            .map(|i: u32| {
+                let (a, b) = ((i * 7) % 13, (i * 11) % 17);
+                format!("{}-{},{}-{}\n", a, a + i % 5, b, b + i % 3)
+            })
+            .collect::<String>();
+        let roster = Roster::new(&super::super::shared::must_parse(parse, &input));
+        let pairs = roster.assignments.iter().tuple_combinations().collect_vec();
+        let overlapping = pairs
+            .iter()
+            .filter(|(a, b)| a.start <= b.end && b.start <= a.end)
+            .count();
+        let containing = pairs
+            .iter()
+            .filter(|(a, b)| {
+                (a.start <= b.start && b.end <= a.end) || (b.start <= a.start && a.end <= b.end)
+            })
+            .count();
+        assert_eq!(roster.count_overlapping(), overlapping as u64);
+        assert_eq!(roster.count_containing(), containing as u64);
+        let (listed_overlapping, listed_containing) = roster.list_pairs(usize::MAX);
+        assert_eq!(listed_overlapping.len(), overlapping);
+        assert_eq!(listed_containing.len(), containing);
+        assert!(listed_containing
+            .iter()
+            .all(|(a, b)| a.start <= b.start && b.end <= a.end));
+    }
+
+    #[test]
+    fn test_plans() {
+        let mut params = Params::new();
+        params.insert("list", "0");
+        params.insert("plan", "shrink");
+        assert!(
+            report("1-4,2-3\n3-6,5-5\n6-9,8-12\n1-12,10-11\n".into(), &params).ends_with(
+                "\
+plan                 shrink
+elves kept           7
+elves shrunk         3
+elves dropped        1
+sections reassigned  0
+
+Conflict-free roster
+  1-1 (line 1, first elf), shrunk from 1-4
+  2-3 (line 1, second elf)
+  4-4 (line 4, first elf), shrunk from 1-12
+  5-5 (line 2, second elf)
+  6-9 (line 3, first elf)
+  10-11 (line 4, second elf)
+  12-12 (line 3, second elf), shrunk from 8-12
+
+Dropped
+  3-6 (line 2, first elf)
+"
+            )
+        );
+
+        // Sections that a kept elf still covers don't count as reassigned,
+        // and shrinking can give the rest back.
+        params.insert("plan", "drop");
+        assert!(report("1-5,4-8\n".into(), &params)
+            .contains("elves dropped        1\nsections reassigned  3\n"));
+        params.insert("plan", "shrink");
+        assert!(report("1-5,4-8\n".into(), &params).contains(
+            "elves dropped        0\nsections reassigned  0\n\n\
+             Conflict-free roster\n  1-5 (line 1, first elf)\n  \
+             6-8 (line 1, second elf), shrunk from 4-8\n"
+        ));
+
+        // Check, on the roster above and on generated ones, that the drop plan
+        // keeps as many elves as any conflict-free subset of the roster, and
+        // that neither plan leaves an overlap or changes more elves than that
+        // leaves out.
+        let conflict_free = |kept: &[Assignment]| {
+            kept.iter()
+                .tuple_combinations()
+                .all(|(a, b)| a.end < b.start || b.end < a.start)
+        };
+        let generated = (0..20).map(|seed: u32| {
+            (0..5)
+                .map(|i| {
+                    let i = seed * 5 + i;
+                    let (a, b) = ((i * 7) % 13, (i * 11) % 17);
+                    format!("{}-{},{}-{}\n", a, a + i % 5, b, b + i % 3)
+                })
+                .collect::<String>()
+        });
+        for input in ["1-4,2-3\n3-6,5-5\n6-9,8-12\n1-12,10-11\n".to_string()]
+            .into_iter()
+            .chain(generated)
+        {
+            let roster = Roster::new(&super::super::shared::must_parse(parse, &input));
+            let most = roster
+                .assignments
+                .iter()
+                .copied()
+                .powerset()
+                .filter(|kept| conflict_free(kept))
+                .map(|kept| kept.len())
+                .max()
+                .unwrap();
+            assert_eq!(roster.drop_plan().iter().flatten().count(), most);
+            for plan in [roster.drop_plan(), roster.shrink_plan()] {
+                assert!(conflict_free(&plan.iter().flatten().copied().collect_vec()));
+                let changed = roster
+                    .assignments
+                    .iter()
+                    .zip(&plan)
+                    .filter(|(a, kept)| **kept != Some(**a))
+                    .count();
+                assert_eq!(changed, roster.assignments.len() - most, "{}", input);
+            }
+        }
+    }
+
+    #[test]
+    fn test_summarize() {
+        assert_eq!(
+            summarize(EXAMPLE_INPUT.into()),
+            Summary::new()
+                .field("pairs", 6)
+                .field("lowest section", 2)
+                .field("highest section", 9)
+                .field("widest assignment", 7)
+        )
+    }
+}
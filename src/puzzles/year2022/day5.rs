@@ -0,0 +1,823 @@
+use std::{collections::VecDeque, ops::Range};
+
+use itertools::Itertools;
+use nom::{
+    branch::alt,
+    bytes::complete::{tag, take_till1},
+    character::complete::{char, newline, none_of, not_line_ending},
+    combinator::{all_consuming, map},
+    sequence::{delimited, tuple},
+};
+
+use super::{
+    inspect::Summary,
+    params::Params,
+    shared::{
+        fail,
+        parsing::{lines, separated_listn, unsigned, IResult, ParseError},
+        trace,
+    },
+    visualize::Visualize,
+};
+
+// The top of the column is the back. Crates are labelled with one or more
+// characters.
+type Column = VecDeque<String>;
+
+// A stack of crates, with the number it's labelled with in the drawing.
+// Steps refer to stacks by their labels, which don't have to be in order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct Stack {
+    label: usize,
+    column: Column,
+}
+
+type Crates = Vec<Stack>;
+
+// A model of crane, which decides how crates move in a single step.
+pub(super) trait Crane {
+    fn name(&self) -> String;
+
+    // Lifts are checked before they reach the crane, so there are always
+    // enough crates to move.
+    fn lift(&self, crates: &mut Crates, lift: &Lift);
+}
+
+// Moves one crate at a time, so crates moved together end up in reverse.
+struct CrateMover9000;
+
+impl Crane for CrateMover9000 {
+    fn name(&self) -> String {
+        "CrateMover 9000".into()
+    }
+
+    fn lift(&self, crates: &mut Crates, lift: &Lift) {
+        for _ in 0..lift.quantity {
+            if let Some(label) = crates[lift.from].column.pop_back() {
+                crates[lift.to].column.push_back(label);
+            }
+        }
+    }
+}
+
+// Moves all of a step's crates at once, keeping their order.
+struct CrateMover9001;
+
+impl Crane for CrateMover9001 {
+    fn name(&self) -> String {
+        "CrateMover 9001".into()
+    }
+
+    fn lift(&self, crates: &mut Crates, lift: &Lift) {
+        let from = &mut crates[lift.from].column;
+        let mut picked_up = from
+            .drain(from.len().saturating_sub(lift.quantity)..)
+            .collect::<Column>();
+        crates[lift.to].column.append(&mut picked_up);
+    }
+}
+
+// Moves up to `capacity` crates at a time, keeping their order within each
+// lift. With a capacity of 1 it's a CrateMover 9000, and with a capacity of
+// at least the biggest step it's a CrateMover 9001.
+struct LimitedCrane {
+    capacity: usize,
+}
+
+impl Crane for LimitedCrane {
+    fn name(&self) -> String {
+        format!("crane lifting up to {} crates", self.capacity)
+    }
+
+    fn lift(&self, crates: &mut Crates, lift: &Lift) {
+        let mut left = lift.quantity;
+        while left > 0 {
+            let lifted = left.min(self.capacity);
+            CrateMover9001.lift(
+                crates,
+                &Lift {
+                    quantity: lifted,
+                    ..*lift
+                },
+            );
+            left -= lifted;
+        }
+    }
+}
+
+// The `crane` parameter picks the model: 9000, 9001, or capacity:N for a
+// crane that lifts up to N crates at a time.
+fn load_crane(params: &Params, default: &str) -> Box<dyn Crane> {
+    let crane = params.get("crane", default.to_string());
+    match crane.as_str() {
+        "9000" => Box::new(CrateMover9000),
+        "9001" => Box::new(CrateMover9001),
+        _ => match crane
+            .strip_prefix("capacity:")
+            .and_then(|capacity| capacity.parse().ok())
+        {
+            Some(capacity) if capacity > 0 => Box::new(LimitedCrane { capacity }),
+            _ => fail!(
+                "Unknown crane {:?} (expected 9000, 9001, or capacity:N for N of at least 1)",
+                crane
+            ),
+        },
+    }
+}
+
+fn simulate_crane(input: String, crane: &dyn Crane) -> String {
+    let (mut crates, steps) = super::shared::must_parse(parse_puzzle, input.as_str());
+    run_steps(&mut crates, &steps, crane);
+    top_crates(&crates)
+}
+
+fn run_steps(crates: &mut Crates, steps: &[Step], crane: &dyn Crane) {
+    for (i, step) in steps.iter().enumerate() {
+        run_step(crates, i + 1, step, crane);
+    }
+}
+
+// Runs the step with the given (1-indexed) number, after checking that it
+// can be done.
+fn run_step(crates: &mut Crates, number: usize, step: &Step, crane: &dyn Crane) {
+    trace!(
+        Info,
+        "move",
+        step = number,
+        quantity = step.quantity,
+        from = step.from,
+        to = step.to,
+    );
+    let lift = Lift::check(crates, number, step);
+    crane.lift(crates, &lift);
+    trace!(
+        Trace,
+        "stacks",
+        step = number,
+        stacks = describe_stacks(crates)
+    );
+}
+
+// The stacks on one line, from the bottom up, like `1:[Z][N] 2:[M] 3:`.
+fn describe_stacks(crates: &[Stack]) -> String {
+    crates
+        .iter()
+        .map(|stack| {
+            format!(
+                "{}:{}",
+                stack.label,
+                stack
+                    .column
+                    .iter()
+                    .map(|label| format!("[{}]", label))
+                    .join("")
+            )
+        })
+        .join(" ")
+}
+
+// The top crate of each stack, with a space for each empty stack.
+fn top_crates(crates: &Crates) -> String {
+    crates
+        .iter()
+        .map(|stack| stack.column.back().map_or(" ", String::as_str))
+        .collect()
+}
+
+// Part 1 uses a CrateMover 9000 unless the `crane` parameter says otherwise.
+pub fn part1(input: String, params: &Params) -> String {
+    simulate_crane(input, load_crane(params, "9000").as_ref())
+}
+
+pub struct Visualizer {
+    crates: Crates,
+    steps: Vec<Step>,
+    done: usize,
+    crane: Box<dyn Crane>,
+}
+
+// Like the parts, the visualizers use the crane the `crane` parameter picks.
+pub fn part1_visualizer(input: String, params: &Params) -> Visualizer {
+    Visualizer::new(input, load_crane(params, "9000"))
+}
+
+pub fn part2_visualizer(input: String, params: &Params) -> Visualizer {
+    Visualizer::new(input, load_crane(params, "9001"))
+}
+
+impl Visualizer {
+    fn new(input: String, crane: Box<dyn Crane>) -> Visualizer {
+        let (crates, steps) = super::shared::must_parse(parse_puzzle, input.as_str());
+        Visualizer {
+            crates,
+            steps,
+            done: 0,
+            crane,
+        }
+    }
+}
+
+impl Visualize for Visualizer {
+    fn step(&mut self) -> bool {
+        match self.steps.get(self.done) {
+            Some(step) => {
+                self.done += 1;
+                run_step(&mut self.crates, self.done, step, self.crane.as_ref());
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn render(&self) -> String {
+        let caption = match self.done.checked_sub(1).map(|i| &self.steps[i]) {
+            Some(step) => format!(
+                "Step {} of {}: move {} from {} to {}",
+                self.done,
+                self.steps.len(),
+                step.quantity,
+                step.from,
+                step.to
+            ),
+            None => format!("Step 0 of {}", self.steps.len()),
+        };
+        format!("{}\n\n{}", caption, render_crates(&self.crates))
+    }
+}
+
+// Draws the stacks the same way the puzzle input does, with the top crates
+// first and the stack numbers last. Each stack is as wide as its widest crate
+// or its number, whichever is wider.
+fn render_crates(crates: &Crates) -> String {
+    let widths = crates
+        .iter()
+        .map(|stack| {
+            let label = stack.label.to_string().len();
+            let widest = stack
+                .column
+                .iter()
+                .map(|label| label.chars().count() + 2)
+                .max();
+            label.max(widest.unwrap_or(3))
+        })
+        .collect_vec();
+    let height = crates
+        .iter()
+        .map(|stack| stack.column.len())
+        .max()
+        .unwrap_or(0);
+    let mut rendered = String::new();
+    for level in (0..height).rev() {
+        let row = crates
+            .iter()
+            .zip(&widths)
+            .map(|(stack, width)| match stack.column.get(level) {
+                Some(label) => format!("{:^width$}", format!("[{}]", label)),
+                None => " ".repeat(*width),
+            })
+            .join(" ");
+        rendered.push_str(row.trim_end());
+        rendered.push('\n');
+    }
+    let numbers = crates
+        .iter()
+        .zip(&widths)
+        .map(|(stack, width)| format!("{:^width$}", stack.label))
+        .join(" ");
+    rendered.push_str(numbers.trim_end());
+    rendered.push('\n');
+    rendered
+}
+
+// Draws the stacks as they end up with each crane, or with the one the
+// `crane` parameter picks.
+pub fn report(input: String, params: &Params) -> String {
+    let (crates, steps) = super::shared::must_parse(parse_puzzle, input.as_str());
+    let cranes = match params.get("crane", String::new()).as_str() {
+        "" => vec![load_crane(params, "9000"), load_crane(params, "9001")],
+        crane => vec![load_crane(params, crane)],
+    };
+    let mut report = format!("Starting stacks\n{}", render_crates(&crates));
+    for crane in cranes {
+        let mut crates = crates.clone();
+        run_steps(&mut crates, &steps, crane.as_ref());
+        report.push_str(&format!(
+            "\nWith a {}: {}\n{}",
+            crane.name(),
+            top_crates(&crates),
+            render_crates(&crates)
+        ));
+    }
+    report
+}
+
+pub fn summarize(input: String) -> Summary {
+    let (crates, steps) = super::shared::must_parse(parse_puzzle, input.as_str());
+    Summary::new()
+        .field("stacks", crates.len())
+        .field(
+            "stack heights",
+            crates.iter().map(|stack| stack.column.len()).join(" "),
+        )
+        .field(
+            "crates",
+            crates.iter().map(|stack| stack.column.len()).sum::<usize>(),
+        )
+        .field("steps", steps.len())
+        .field(
+            "crates moved",
+            steps.iter().map(|step| step.quantity).sum::<usize>(),
+        )
+}
+
+pub fn dump(input: String) -> String {
+    format!(
+        "{:#?}",
+        super::shared::must_parse(parse_puzzle, input.as_str())
+    )
+}
+
+// The drawing is lined up by its last row, which numbers the stacks: each
+// crate belongs to the stack whose number is underneath it. Rows don't need
+// trailing spaces, and stack numbers can be more than one digit, as long as
+// every crate is over exactly one of them.
+pub(super) fn parse_puzzle(input: &str) -> IResult<'_, (Crates, Vec<Step>)> {
+    // Each row is kept as the input from the start of the row, so that errors
+    // can say where they are.
+    let (input, mut rows) = lines(|row| map(not_line_ending, |_| row)(row))(input)?;
+    let labels = match rows.pop() {
+        Some(numbers) => stack_labels(numbers).map_err(nom::Err::Failure)?,
+        None => Vec::new(),
+    };
+    let mut crates = labels
+        .iter()
+        .map(|(label, _)| Stack {
+            label: *label,
+            column: Column::new(),
+        })
+        .collect_vec();
+    // Stack the crates from the bottom row up.
+    for row in rows.iter().rev() {
+        let row = match padded_row(row, &labels) {
+            Some(row) => row,
+            None => row_of_crates(row, &labels).map_err(nom::Err::Failure)?,
+        };
+        for (i, label) in row {
+            crates[i].column.push_back(label);
+        }
+    }
+
+    // Parse empty line separator.
+    let (input, _) = newline(input)?;
+
+    // Parse steps, numbering their lines from after the drawing and the empty
+    // line.
+    let (input, mut steps) = lines(step)(input)?;
+    let first_line = rows.len() + 3;
+    for (i, step) in steps.iter_mut().enumerate() {
+        step.line = first_line + i;
+    }
+
+    Ok((input, (crates, steps)))
+}
+
+// Splits off the row at the start of the input, and makes errors for parts
+// of the row.
+fn split_row<'a>(input: &'a str) -> (&'a str, impl Fn(&str, &'static str) -> ParseError<'a>) {
+    let row = input.split('\n').next().unwrap_or_default();
+    let error =
+        move |rest: &str, expected| ParseError::context(&input[row.len() - rest.len()..], expected);
+    (row, error)
+}
+
+// The number of characters before `rest` in `row`, for lining crates up with
+// stack numbers.
+fn column_of(row: &str, rest: &str) -> usize {
+    row[..row.len() - rest.len()].chars().count()
+}
+
+// Each stack's number, and the columns it takes up in the drawing.
+fn stack_labels(input: &str) -> Result<Vec<(usize, Range<usize>)>, ParseError<'_>> {
+    let (row, error) = split_row(input);
+    let mut labels: Vec<(usize, Range<usize>)> = Vec::new();
+    let mut rest = row.trim_start_matches(' ');
+    while !rest.is_empty() {
+        let (after, label) = unsigned(rest).map_err(|_| error(rest, "a stack number"))?;
+        if labels.iter().any(|(other, _)| *other == label) {
+            return Err(error(rest, "a stack number not used before"));
+        }
+        if !after.is_empty() && !after.starts_with(' ') {
+            return Err(error(after, "a space after the stack number"));
+        }
+        labels.push((label, column_of(row, rest)..column_of(row, after)));
+        rest = after.trim_start_matches(' ');
+    }
+    if labels.is_empty() {
+        return Err(error(row, "a row of stack numbers"));
+    }
+    Ok(labels)
+}
+
+// Most drawings number their stacks from 1 to 9 and pad every row to full
+// width, with a three-character slot for each stack. Those rows are read slot
+// by slot. Returns None for any other row, to be lined up with the stack
+// numbers by `row_of_crates` instead.
+fn padded_row(input: &str, labels: &[(usize, Range<usize>)]) -> Option<Vec<(usize, String)>> {
+    let usual_layout = labels
+        .iter()
+        .enumerate()
+        .all(|(i, (_, columns))| *columns == (4 * i + 1..4 * i + 2));
+    if !usual_layout {
+        return None;
+    }
+    let (row, _) = split_row(input);
+    let (_, slots) = all_consuming(separated_listn(char(' '), slot, labels.len()))(row).ok()?;
+    Some(
+        slots
+            .into_iter()
+            .enumerate()
+            .filter_map(|(i, label)| Some((i, label?)))
+            .collect(),
+    )
+}
+
+// A crate like [A], or three spaces where a stack has no crate.
+fn slot(input: &str) -> IResult<'_, Option<String>> {
+    alt((
+        map(delimited(char('['), none_of("] "), char(']')), |label| {
+            Some(label.to_string())
+        }),
+        map(tag("   "), |_| None),
+    ))(input)
+}
+
+// The crates in one row of the drawing, as the index of the stack each is in
+// and its label.
+fn row_of_crates<'a>(
+    input: &'a str,
+    labels: &[(usize, Range<usize>)],
+) -> Result<Vec<(usize, String)>, ParseError<'a>> {
+    let (row, error) = split_row(input);
+    let mut crates: Vec<(usize, String)> = Vec::new();
+    let mut rest = row.trim_start_matches(' ');
+    while !rest.is_empty() {
+        let (after, label) = delimited(char('['), take_till1(|c| c == ']'), char(']'))(rest)
+            .map_err(|_: nom::Err<ParseError>| error(rest, "a crate like [A]"))?;
+        let columns = column_of(row, rest)..column_of(row, after);
+        let mut under = labels
+            .iter()
+            .positions(|(_, number)| number.start < columns.end && columns.start < number.end);
+        let i = match (under.next(), under.next()) {
+            (Some(i), None) => i,
+            _ => return Err(error(rest, "a crate over exactly one stack number")),
+        };
+        if crates.iter().any(|(other, _)| *other == i) {
+            return Err(error(rest, "one crate per stack in each row"));
+        }
+        crates.push((i, label.to_string()));
+        rest = after.trim_start_matches(' ');
+    }
+    Ok(crates)
+}
+
+fn step(input: &str) -> IResult<'_, Step> {
+    map(
+        tuple((
+            tag("move "),
+            unsigned,
+            tag(" from "),
+            unsigned::<usize>,
+            tag(" to "),
+            unsigned::<usize>,
+        )),
+        |(_, quantity, _, from, _, to)| Step {
+            quantity,
+            from,
+            to,
+            line: 0,
+        },
+    )(input)
+}
+
+// Part 2 uses a CrateMover 9001 unless the `crane` parameter says otherwise.
+pub fn part2(input: String, params: &Params) -> String {
+    simulate_crane(input, load_crane(params, "9001").as_ref())
+}
+
+// A step as the input gives it, with stacks given by their labels.
+#[derive(Debug)]
+pub(super) struct Step {
+    quantity: usize,
+    from: usize,
+    to: usize,
+    // The 1-indexed line of the input that the step is on.
+    line: usize,
+}
+
+// A step that has been checked against the stacks, with stacks as indices.
+#[derive(Debug, Clone, Copy)]
+pub(super) struct Lift {
+    quantity: usize,
+    from: usize,
+    to: usize,
+}
+
+impl Lift {
+    // Fails unless both stacks exist and are different, and the stack the
+    // crates come from has enough of them.
+    fn check(crates: &Crates, number: usize, step: &Step) -> Lift {
+        let stack =
+            |label: usize, role: &str| match crates.iter().position(|stack| stack.label == label) {
+                Some(i) => i,
+                None => fail!(
+                    "Step {} (line {}): no stack {} to move {} (stacks are {})",
+                    number,
+                    step.line,
+                    label,
+                    role,
+                    crates.iter().map(|stack| stack.label).join(", ")
+                ),
+            };
+        let (from, to) = (stack(step.from, "from"), stack(step.to, "to"));
+        if from == to {
+            fail!(
+                "Step {} (line {}): moves crates from stack {} to itself",
+                number,
+                step.line,
+                step.from
+            );
+        }
+        if crates[from].column.len() < step.quantity {
+            fail!(
+                "Step {} (line {}): moves {} crates from stack {}, which only has {}",
+                number,
+                step.line,
+                step.quantity,
+                step.from,
+                crates[from].column.len()
+            );
+        }
+        Lift {
+            quantity: step.quantity,
+            from,
+            to,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{super::shared::catch_failure, *};
+
+    const EXAMPLE_INPUT: &str = "    [D]    
+[N] [C]    
+[Z] [M] [P]
+ 1   2   3 
+
+move 1 from 2 to 1
+move 3 from 1 to 3
+move 2 from 2 to 1
+move 1 from 1 to 2
+";
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(part1(EXAMPLE_INPUT.into(), &Params::new()), "CMZ")
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(part2(EXAMPLE_INPUT.into(), &Params::new()), "MCD")
+    }
+
+    #[test]
+    fn test_cranes() {
+        let input = "[A]
+[B]
+[C]
+[D]
+[E]
+[F]
+ 1   2
+
+move 5 from 1 to 2
+";
+        let mut params = Params::new();
+        for (crane, top) in [
+            ("9001", "FA"),
+            ("capacity:1", "FE"),
+            ("capacity:2", "FE"),
+            ("capacity:3", "FD"),
+            ("capacity:5", "FA"),
+        ] {
+            params.insert("crane", crane);
+            assert_eq!(part1(input.into(), &params), top, "{}", crane);
+        }
+        params.insert("crane", "capacity:2");
+        assert_eq!(
+            report(input.into(), &params),
+            "\
+Starting stacks
+[A]
+[B]
+[C]
+[D]
+[E]
+[F]
+ 1   2
+
+With a crane lifting up to 2 crates: FE
+    [E]
+    [C]
+    [D]
+    [A]
+[F] [B]
+ 1   2
+"
+        );
+    }
+
+    #[test]
+    fn test_invalid_steps() {
+        let fails = |steps: &str| {
+            let input = EXAMPLE_INPUT.replace("move 1 from 1 to 2\n", steps);
+            catch_failure(|| part2(input, &Params::new())).unwrap_err()
+        };
+        assert_eq!(
+            fails("move 4 from 1 to 2\n"),
+            "Step 4 (line 9): moves 4 crates from stack 1, which only has 2"
+        );
+        assert_eq!(
+            fails("move 1 from 4 to 2\n"),
+            "Step 4 (line 9): no stack 4 to move from (stacks are 1, 2, 3)"
+        );
+        assert_eq!(
+            fails("move 1 from 1 to 0\n"),
+            "Step 4 (line 9): no stack 0 to move to (stacks are 1, 2, 3)"
+        );
+        assert_eq!(
+            fails("move 1 from 3 to 3\n"),
+            "Step 4 (line 9): moves crates from stack 3 to itself"
+        );
+    }
+
+    #[test]
+    fn test_empty_stacks() {
+        // Moving everything off the first stack leaves a gap in the message.
+        let input = EXAMPLE_INPUT.replace("move 1 from 1 to 2\n", "move 2 from 1 to 2\n");
+        assert_eq!(part1(input, &Params::new()), " CZ");
+    }
+
+    #[test]
+    fn test_tolerant_drawing() {
+        // Rows without trailing spaces, more than nine stacks, crates with
+        // longer labels, and stacks numbered out of order.
+        let input = "                                          [K]
+[A]                                       [L] [AB]
+[B] [C] [D] [E] [F] [G] [H] [I] [J]       [M] [CD]
+ 1   2   3   4   5   6   7   8   9   10   12  11
+
+move 1 from 12 to 10
+move 2 from 11 to 1
+";
+        let (crates, steps) = super::super::shared::must_parse(parse_puzzle, input);
+        assert_eq!(
+            crates.iter().map(|stack| stack.label).collect_vec(),
+            [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 12, 11]
+        );
+        assert_eq!(describe_stacks(&crates[10..]), "12:[M][L][K] 11:[CD][AB]");
+        assert_eq!(steps[1].line, 7);
+        assert_eq!(part2(input.into(), &Params::new()), "ABCDEFGHIJKL ");
+
+        // Drawing the stacks lines them up again, however wide they are.
+        let mut wide = crates.clone();
+        wide[0].column.push_back("LONG".into());
+        let redrawn = render_crates(&wide);
+        assert_eq!(
+            redrawn,
+            "\
+[LONG]                                     [K]
+ [A]                                       [L] [AB]
+ [B]   [C] [D] [E] [F] [G] [H] [I] [J]     [M] [CD]
+  1     2   3   4   5   6   7   8   9  10  12   11
+"
+        );
+        let (reparsed, _) =
+            super::super::shared::must_parse(parse_puzzle, &(redrawn + "\nmove 1 from 1 to 2\n"));
+        assert_eq!(reparsed, wide);
+    }
+
+    #[test]
+    fn test_padded_row() {
+        let labels = [(1, 1..2), (2, 5..6), (3, 9..10)];
+        assert_eq!(
+            padded_row("[Z] [M] [P]\n", &labels),
+            Some(vec![(0, "Z".into()), (1, "M".into()), (2, "P".into())])
+        );
+        assert_eq!(
+            padded_row("    [D]    \n", &labels),
+            Some(vec![(1, "D".into())])
+        );
+        // Trimmed rows are lined up by column instead.
+        assert_eq!(padded_row("    [D]\n", &labels), None);
+    }
+
+    #[test]
+    fn test_invalid_drawing() {
+        let fails = |drawing: &str| {
+            catch_failure(|| {
+                part1(
+                    format!("{}\n\nmove 1 from 1 to 2\n", drawing),
+                    &Params::new(),
+                )
+            })
+            .unwrap_err()
+        };
+        assert_eq!(
+            fails("[A] [B]\n 1 2"),
+            "Could not parse puzzle input: line 1, column 5: \
+             expected a crate over exactly one stack number, found \"[B]\""
+        );
+        assert_eq!(
+            fails("[A]\n 1   1"),
+            "Could not parse puzzle input: line 2, column 6: \
+             expected a stack number not used before, found \"1\""
+        );
+        assert_eq!(
+            fails("[A] B\n 1   2"),
+            "Could not parse puzzle input: line 1, column 5: \
+             expected a crate like [A], found \"B\""
+        );
+    }
+
+    #[test]
+    fn test_describe_stacks() {
+        let (crates, _) = super::super::shared::must_parse(parse_puzzle, EXAMPLE_INPUT);
+        assert_eq!(describe_stacks(&crates), "1:[Z][N] 2:[M][C][D] 3:[P]");
+    }
+
+    #[test]
+    fn test_report() {
+        assert_eq!(
+            report(EXAMPLE_INPUT.into(), &Params::new()),
+            "\
+Starting stacks
+    [D]
+[N] [C]
+[Z] [M] [P]
+ 1   2   3
+
+With a CrateMover 9000: CMZ
+        [Z]
+        [N]
+        [D]
+[C] [M] [P]
+ 1   2   3
+
+With a CrateMover 9001: MCD
+        [D]
+        [N]
+        [Z]
+[M] [C] [P]
+ 1   2   3
+"
+        )
+    }
+
+    #[test]
+    fn test_summarize() {
+        assert_eq!(
+            summarize(EXAMPLE_INPUT.into()),
+            Summary::new()
+                .field("stacks", 3)
+                .field("stack heights", "2 3 1")
+                .field("crates", 6)
+                .field("steps", 4)
+                .field("crates moved", 7)
+        )
+    }
+
+    #[test]
+    fn test_visualizer() {
+        let mut visualizer = part2_visualizer(EXAMPLE_INPUT.into(), &Params::new());
+        while visualizer.step() {}
+        assert_eq!(
+            visualizer.render(),
+            "Step 4 of 4: move 1 from 1 to 2
+
+        [D]
+        [N]
+        [Z]
+[M] [C] [P]
+ 1   2   3
+"
+        );
+
+        // The visualizer runs the crane that the parameters pick.
+        let mut params = Params::new();
+        params.insert("crane", "9000");
+        let mut visualizer = part2_visualizer(EXAMPLE_INPUT.into(), &params);
+        while visualizer.step() {}
+        assert!(visualizer.render().ends_with("[C] [M] [P]\n 1   2   3\n"));
+    }
+}
@@ -1,4 +1,6 @@
-use std::{collections::HashSet, process::exit};
+use std::collections::HashSet;
+
+use super::shared::fail;
 
 pub fn part1(input: String) -> usize {
     find_marker(4, input)
@@ -20,8 +22,7 @@ pub fn find_marker(window_size: usize, input: String) -> usize {
             return i + window_size;
         }
     }
-    println!("Impossible: no marker value detected");
-    exit(1)
+    fail!("Impossible: no marker value detected")
 }
 
 #[cfg(test)]
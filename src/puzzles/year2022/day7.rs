@@ -0,0 +1,469 @@
+use std::{
+    cell::RefCell,
+    cmp::min,
+    collections::{BTreeMap, HashMap},
+    rc::{Rc, Weak},
+};
+
+use itertools::Itertools;
+use nom::{
+    branch::alt,
+    bytes::complete::{tag, take_while1},
+    character::{
+        complete::{alpha1, char, newline},
+        is_alphanumeric,
+    },
+    combinator::map,
+    multi::many1,
+    sequence::{delimited, preceded, separated_pair, terminated},
+};
+
+use super::{
+    explore::{expect_args, Explore},
+    inspect::Summary,
+    shared::{
+        checked, fail,
+        parsing::{unsigned, IResult},
+    },
+};
+
+pub fn part1(input: String) -> TotalSize {
+    let root = parse_filesystem(input.as_str());
+
+    // Traverse the tree from the bottom up, computing total sizes.
+    let (_, counted_size) = traverse_filesystem(
+        &root.borrow(),
+        0,
+        add_sizes,
+        |total_size, subdir_counted_size| {
+            if total_size <= 100_000 {
+                add_sizes(total_size, subdir_counted_size)
+            } else {
+                subdir_counted_size
+            }
+        },
+    );
+
+    counted_size
+}
+
+pub fn part2(input: String) -> TotalSize {
+    let root = parse_filesystem(input.as_str());
+
+    // Compute space needed.
+    let space_available = 70_000_000;
+    let space_needed = 30_000_000;
+    let (space_used, _) = traverse_filesystem(&root.borrow(), 0, |_, _| 0, |_, _| 0);
+    // If there's already enough free space, nothing needs deleting.
+    let Some(min_space_to_delete) = space_used.checked_sub(space_available - space_needed) else {
+        return 0;
+    };
+
+    // Find the smallest directory larger than the threshold.
+    let (_, smallest_to_delete_size) = traverse_filesystem(
+        &root.borrow(),
+        TotalSize::MAX,
+        min,
+        |total_size, subdir_smallest_deletable_size| {
+            if total_size > min_space_to_delete {
+                min(total_size, subdir_smallest_deletable_size)
+            } else {
+                subdir_smallest_deletable_size
+            }
+        },
+    );
+
+    smallest_to_delete_size
+}
+
+// File sizes are u32s, but directories can add up to more than that.
+type TotalSize = u64;
+
+fn add_sizes(a: TotalSize, b: TotalSize) -> TotalSize {
+    checked!(a.checked_add(b), "adding sizes {} and {}", a, b)
+}
+
+fn direct_size(dir: &Directory) -> TotalSize {
+    dir.files
+        .values()
+        .fold(0, |total, size| add_sizes(total, *size as TotalSize))
+}
+
+fn traverse_filesystem<T, F, F2>(
+    dir: &Directory,
+    combine_result_init: T,
+    combine_result: F,
+    make_result: F2,
+) -> (TotalSize, T)
+where
+    T: Copy,
+    F: Fn(T, T) -> T + Copy,
+    F2: Fn(TotalSize, T) -> T + Copy,
+{
+    let direct_size = direct_size(dir);
+    let (subdir_total_size, subdir_result) = dir
+        .dirs
+        .values()
+        .map(|subdir| {
+            traverse_filesystem(
+                &subdir.borrow(),
+                combine_result_init,
+                combine_result,
+                make_result,
+            )
+        })
+        .fold((0, combine_result_init), |(a, b), (c, d)| {
+            (add_sizes(a, c), combine_result(b, d))
+        });
+
+    let total_size = add_sizes(direct_size, subdir_total_size);
+    (total_size, make_result(total_size, subdir_result))
+}
+
+#[derive(Debug)]
+pub(super) struct Directory<'a> {
+    // We need the Rc because multiple children can have pointers to their
+    // parents, and the RefCell because the parent needs interior mutation
+    // during construction in order to add more entries while some of the
+    // children (who have already been constructed) hold a pointer to the
+    // parent.
+    //
+    // If we interpret the puzzle's semantics strictly, there is no way to
+    // guarantee that we can construct all children before their parents,
+    // because the terminal commands don't have a guaranteed traversal order.
+    //
+    // Alternative implementations include:
+    //
+    // - Using a Zipper.
+    // - Using a Vec-backed tree implementation (each node has its own index,
+    //   and refers to other nodes by index).
+    // - Using a Map-backed tree implementation (each node is keyed by its
+    //   directory path, and refers to other nodes by path).
+    //
+    // We only use the parent pointer during the _construction_ of the tree -
+    // once constructed, should we throw it away somehow?
+    parent: Option<Weak<RefCell<Directory<'a>>>>,
+    files: HashMap<&'a str, u32>,
+    dirs: HashMap<&'a str, Rc<RefCell<Directory<'a>>>>,
+}
+
+#[derive(Debug, PartialEq)]
+enum ChangeDirTarget<'a> {
+    In { dir: &'a str },
+    Out,
+    Root,
+}
+use ChangeDirTarget::*;
+
+#[derive(Debug, PartialEq)]
+enum ListEntry<'a> {
+    File { name: &'a str, size: u32 },
+    Directory { name: &'a str },
+}
+
+#[derive(Debug, PartialEq)]
+enum Command<'a> {
+    ChangeDir(ChangeDirTarget<'a>),
+    List(Vec<ListEntry<'a>>),
+}
+
+use Command::*;
+
+// The REPL explorer flattens the tree into listings keyed by absolute path, so
+// that it doesn't borrow from the puzzle input.
+pub struct Explorer {
+    listings: BTreeMap<String, Listing>,
+}
+
+struct Listing {
+    total_size: TotalSize,
+    files: Vec<(String, u32)>,
+    dirs: Vec<String>,
+}
+
+impl Explorer {
+    pub fn new(input: String) -> Explorer {
+        let root = parse_filesystem(input.as_str());
+        let mut listings = BTreeMap::new();
+        list_directory(&root.borrow(), String::from("/"), &mut listings);
+        Explorer { listings }
+    }
+
+    fn listing(&self, path: &str) -> Result<&Listing, String> {
+        let path = format!("/{}", path.trim_matches('/'));
+        self.listings
+            .get(&path)
+            .ok_or_else(|| format!("No such directory: {}", path))
+    }
+}
+
+fn list_directory(
+    dir: &Directory,
+    path: String,
+    listings: &mut BTreeMap<String, Listing>,
+) -> TotalSize {
+    let mut total_size = direct_size(dir);
+    for (name, subdir) in &dir.dirs {
+        let subdir_path = format!("{}/{}", path.trim_end_matches('/'), name);
+        total_size = add_sizes(
+            total_size,
+            list_directory(&subdir.borrow(), subdir_path, listings),
+        );
+    }
+    listings.insert(
+        path,
+        Listing {
+            total_size,
+            files: dir
+                .files
+                .iter()
+                .map(|(name, size)| (name.to_string(), *size))
+                .sorted()
+                .collect(),
+            dirs: dir
+                .dirs
+                .keys()
+                .map(|name| name.to_string())
+                .sorted()
+                .collect(),
+        },
+    );
+    total_size
+}
+
+impl Explore for Explorer {
+    fn commands(&self) -> Vec<(&'static str, &'static str)> {
+        vec![
+            ("size <path>", "Total size of a directory, like /a/e"),
+            ("ls <path>", "List a directory's entries"),
+        ]
+    }
+
+    fn run(&self, command: &str, args: &[&str]) -> Option<Result<String, String>> {
+        match command {
+            "size" => Some(expect_args(args, "size <path>").and_then(|[path]| {
+                self.listing(path)
+                    .map(|listing| listing.total_size.to_string())
+            })),
+            "ls" => Some(expect_args(args, "ls <path>").and_then(|[path]| {
+                self.listing(path).map(|listing| {
+                    listing
+                        .dirs
+                        .iter()
+                        .map(|name| format!("dir {}", name))
+                        .chain(
+                            listing
+                                .files
+                                .iter()
+                                .map(|(name, size)| format!("{} {}", size, name)),
+                        )
+                        .join("\n")
+                })
+            })),
+            _ => None,
+        }
+    }
+}
+
+pub fn summarize(input: String) -> Summary {
+    let root = parse_filesystem(input.as_str());
+    let (directories, files, depth) = count_filesystem(&root.borrow());
+    let (total_size, _) = traverse_filesystem(&root.borrow(), (), |_, _| (), |_, _| ());
+    Summary::new()
+        .field("directories", directories)
+        .field("files", files)
+        .field("tree depth", depth)
+        .field("total size", total_size)
+}
+
+// Counts directories (including this one), files and the depth of the tree
+// rooted at this directory.
+fn count_filesystem(dir: &Directory) -> (usize, usize, usize) {
+    dir.dirs
+        .values()
+        .map(|subdir| count_filesystem(&subdir.borrow()))
+        .fold(
+            (1, dir.files.len(), 0),
+            |(d, f, depth), (d2, f2, depth2)| (d + d2, f + f2, depth.max(depth2 + 1)),
+        )
+}
+
+pub fn dump(input: String) -> String {
+    format!("{:#?}", parse_filesystem(input.as_str()).borrow())
+}
+
+pub(super) fn parse_filesystem(input: &str) -> Rc<RefCell<Directory<'_>>> {
+    let commands = super::shared::must_parse(parse_commands, input);
+    build_filesystem(commands)
+}
+
+fn build_filesystem(commands: Vec<Command>) -> Rc<RefCell<Directory>> {
+    // Create root node.
+    assert_eq!(commands[0], ChangeDir(Root));
+    let root = Rc::new(RefCell::new(Directory {
+        parent: None,
+        files: HashMap::new(),
+        dirs: HashMap::new(),
+    }));
+
+    // Build a tree, going up and down inodes and filling out file entries as
+    // list output directs us.
+    let mut current_ptr = root.clone();
+    for command in &commands[1..] {
+        match command {
+            List(entries) => {
+                let mut current = current_ptr.borrow_mut();
+                for entry in entries {
+                    match entry {
+                        ListEntry::File { name, size } => {
+                            current.files.insert(name, *size);
+                        }
+                        ListEntry::Directory { name } => {
+                            let child = Rc::new(RefCell::new(Directory {
+                                parent: Some(Rc::downgrade(&current_ptr)),
+                                files: HashMap::new(),
+                                dirs: HashMap::new(),
+                            }));
+                            current.dirs.insert(name, child);
+                        }
+                    }
+                }
+            }
+            ChangeDir(In { dir }) => {
+                current_ptr = {
+                    let current = current_ptr.borrow();
+                    current
+                        .dirs
+                        .get(dir)
+                        .unwrap_or_else(|| {
+                            fail!("Impossible: navigated into non-existent directory")
+                        })
+                        .clone()
+                };
+            }
+            ChangeDir(Out) => {
+                current_ptr = {
+                    let current = current_ptr.borrow();
+                    Weak::upgrade(
+                        current.parent.as_ref().unwrap_or_else(|| {
+                            fail!("Impossible: navigated outside of filesystem")
+                        }),
+                    )
+                    .unwrap_or_else(|| fail!("Impossible: directory parent dropped before child"))
+                }
+            }
+            ChangeDir(Root) => {
+                current_ptr = root.clone();
+            }
+        }
+    }
+
+    root
+}
+
+fn parse_commands(input: &str) -> IResult<'_, Vec<Command<'_>>> {
+    many1(preceded(
+        tag("$ "),
+        alt((
+            map(delimited(tag("cd "), alpha1, newline), |dir| {
+                ChangeDir(In { dir })
+            }),
+            map(tag("cd ..\n"), |_| ChangeDir(Out)),
+            map(tag("cd /\n"), |_| ChangeDir(Root)),
+            preceded(
+                tag("ls\n"),
+                map(
+                    many1(terminated(
+                        alt((
+                            map(
+                                separated_pair(unsigned, char(' '), filename),
+                                |(size, name)| ListEntry::File { name, size },
+                            ),
+                            map(preceded(tag("dir "), filename), |name| {
+                                ListEntry::Directory { name }
+                            }),
+                        )),
+                        newline,
+                    )),
+                    List,
+                ),
+            ),
+        )),
+    ))(input)
+}
+
+fn filename(input: &str) -> IResult<'_, &str> {
+    take_while1(|c: char| is_alphanumeric(c as u8) || c == '.')(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE_INPUT: &str = "$ cd /
+$ ls
+dir a
+14848514 b.txt
+8504156 c.dat
+dir d
+$ cd a
+$ ls
+dir e
+29116 f
+2557 g
+62596 h.lst
+$ cd e
+$ ls
+584 i
+$ cd ..
+$ cd ..
+$ cd d
+$ ls
+4060174 j
+8033020 d.log
+5626152 d.ext
+7214296 k
+";
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(part1(EXAMPLE_INPUT.into()), 95437)
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(part2(EXAMPLE_INPUT.into()), 24933642)
+    }
+
+    #[test]
+    fn test_large_sizes() {
+        // More than a u32 can hold in total.
+        let input = "$ cd /\n$ ls\ndir a\n4000000000 b\n$ cd a\n$ ls\n4000000000 c\n";
+        assert_eq!(part2(input.into()), 8000000000);
+        assert_eq!(part2("$ cd /\n$ ls\n10 a\n".into()), 0);
+    }
+
+    #[test]
+    fn test_summarize() {
+        assert_eq!(
+            summarize(EXAMPLE_INPUT.into()),
+            Summary::new()
+                .field("directories", 4)
+                .field("files", 10)
+                .field("tree depth", 2)
+                .field("total size", 48381165)
+        )
+    }
+
+    #[test]
+    fn test_explorer() {
+        let explorer = Explorer::new(EXAMPLE_INPUT.into());
+        assert_eq!(explorer.run("size", &["/a/e"]), Some(Ok("584".into())));
+        assert_eq!(explorer.run("size", &["/"]), Some(Ok("48381165".into())));
+        assert_eq!(
+            explorer.run("ls", &["a"]),
+            Some(Ok("dir e\n29116 f\n2557 g\n62596 h.lst".into()))
+        );
+        assert!(matches!(explorer.run("size", &["/x"]), Some(Err(_))));
+    }
+}
@@ -0,0 +1,184 @@
+use std::collections::HashSet;
+
+use super::{
+    inspect::Summary,
+    shared::{
+        fail,
+        geometry::Direction,
+        grid::{Grid, Position},
+        image::{Image, ImageOptions},
+        must_parse,
+        parsing::char_grid,
+    },
+};
+
+pub fn part1(input: String) -> usize {
+    // Parse input into grid. Top-left is (0, 0). Positive directions are
+    // rightwards and downwards.
+    let grid = parse_rectangular_grid(input.as_str());
+    visible_trees(&grid).len()
+}
+
+fn visible_trees(grid: &Grid<Height>) -> HashSet<Position> {
+    // From each tree on the edge, find all positions of trees visible in all
+    // directions from that tree.
+    //
+    // Take the union of the set of visible trees from each edge tree. This
+    // avoids double-counting trees that are visible from multiple directions.
+    let edges = trees_on_edge(grid);
+    let visible_from_edges = edges
+        .iter()
+        .flat_map(|position| trees_visible_from(grid, *position))
+        .collect::<HashSet<_>>();
+
+    // All edge trees are also visible.
+    visible_from_edges.union(&edges).copied().collect()
+}
+
+// Colors trees by height, and dims the trees that can't be seen from outside
+// the grid.
+pub fn part1_image(input: String, options: &ImageOptions) -> Image {
+    let grid = parse_rectangular_grid(input.as_str());
+    let visible = visible_trees(&grid);
+    let image = Image::from_cells(
+        grid.width(),
+        grid.iter()
+            .map(|(position, height)| {
+                let color = options.color_map.scaled(*height as f64, 0.0, 9.0);
+                if visible.contains(&position) {
+                    color
+                } else {
+                    color.dim(0.25)
+                }
+            })
+            .collect(),
+    );
+    image.downsample(options.downsample)
+}
+
+// A heat map of every tree's scenic score.
+pub fn part2_image(input: String, options: &ImageOptions) -> Image {
+    let grid = parse_rectangular_grid(input.as_str());
+    let scores = Grid::from_cells(
+        grid.width(),
+        grid.positions()
+            .map(|position| scenic_score(&grid, position) as f64)
+            .collect(),
+    );
+    let max_score = scores.values().copied().fold(0.0, f64::max);
+    scores
+        .map(|score| options.color_map.scaled(*score, 0.0, max_score))
+        .downsample(options.downsample)
+}
+
+type Height = u32;
+
+pub fn summarize(input: String) -> Summary {
+    let grid = parse_rectangular_grid(input.as_str());
+    Summary::new()
+        .field("width", grid.width())
+        .field("height", grid.height())
+        .field("trees", grid.values().count())
+        .field("shortest tree", grid.values().min().unwrap_or(&0))
+        .field("tallest tree", grid.values().max().unwrap_or(&0))
+}
+
+pub fn dump(input: String) -> String {
+    format!("{:#?}", parse_rectangular_grid(input.as_str()))
+}
+
+pub(super) fn parse_rectangular_grid(input: &str) -> Grid<Height> {
+    must_parse(char_grid(|_, element| element.to_digit(10)), input)
+}
+
+fn trees_on_edge(grid: &Grid<Height>) -> HashSet<Position> {
+    grid.positions()
+        .filter(|position| grid.is_edge(*position))
+        .collect()
+}
+
+fn trees_visible_from(grid: &Grid<Height>, position: Position) -> HashSet<Position> {
+    Direction::ALL
+        .iter()
+        .flat_map(|direction| {
+            trees_visible_along(grid, position, grid.ray(position, direction.vector()))
+        })
+        .collect::<HashSet<_>>()
+}
+
+fn trees_visible_along(
+    grid: &Grid<Height>,
+    position: Position,
+    line_of_sight: impl Iterator<Item = Position>,
+) -> HashSet<Position> {
+    let start_height = grid[position];
+    let mut visible = HashSet::new();
+    line_of_sight.fold(start_height, |last_highest, position| {
+        let height = grid[position];
+        if height > last_highest {
+            visible.insert(position);
+            height
+        } else {
+            last_highest
+        }
+    });
+    visible
+}
+
+pub fn part2(input: String) -> usize {
+    // Parse input into grid.
+    let grid = parse_rectangular_grid(input.as_str());
+
+    // Find the tree with the largest scenic score.
+    grid.positions()
+        .map(|position| scenic_score(&grid, position))
+        .max()
+        .unwrap_or_else(|| fail!("Impossible: no tree had a maximum scenic score"))
+}
+
+fn scenic_score(grid: &Grid<Height>, position: Position) -> usize {
+    Direction::ALL
+        .iter()
+        .map(|direction| {
+            trees_viewable_along(grid, position, grid.ray(position, direction.vector()))
+        })
+        .product()
+}
+
+fn trees_viewable_along(
+    grid: &Grid<Height>,
+    position: Position,
+    line_of_sight: impl Iterator<Item = Position>,
+) -> usize {
+    let start_height = grid[position];
+    let mut visible_count = 0;
+    for position in line_of_sight {
+        visible_count += 1;
+        if grid[position] >= start_height {
+            break;
+        }
+    }
+    visible_count
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE_INPUT: &str = "30373
+25512
+65332
+33549
+35390
+";
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(part1(EXAMPLE_INPUT.into()), 21)
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(part2(EXAMPLE_INPUT.into()), 8)
+    }
+}
@@ -0,0 +1,251 @@
+use std::collections::HashSet;
+
+use nom::{
+    character::complete::{char, one_of},
+    combinator::{map, map_opt},
+    sequence::separated_pair,
+};
+
+use super::{
+    inspect::Summary,
+    shared::{
+        fail,
+        geometry::{Direction, Direction::*, Point},
+        grid::SparseGrid,
+        parsing::{lines, unsigned, IResult},
+        trace,
+    },
+    visualize::Visualize,
+};
+
+pub fn part1(input: String) -> usize {
+    simulate_rope(input.as_str(), 2)
+}
+
+pub fn part2(input: String) -> usize {
+    simulate_rope(input.as_str(), 10)
+}
+
+fn simulate_rope(input: &str, knots: usize) -> usize {
+    let steps = super::shared::must_parse(parse, input);
+
+    let mut rope = Rope::new(knots);
+    for step in steps {
+        trace!(
+            Info,
+            "move",
+            direction = format!("{:?}", step.direction),
+            distance = step.distance,
+        );
+        for _ in 0..step.distance {
+            rope.pull(step.direction);
+        }
+    }
+    rope.tails.len()
+}
+
+// The first knot is the head, and the last knot is the tail.
+struct Rope {
+    knots: Vec<Position>,
+    tails: HashSet<Position>,
+}
+
+impl Rope {
+    fn new(knots: usize) -> Rope {
+        Rope {
+            knots: vec![Point::ORIGIN; knots],
+            tails: HashSet::from([Point::ORIGIN]),
+        }
+    }
+
+    // Moves the head one step, and every other knot follows the one before it.
+    fn pull(&mut self, direction: Direction) {
+        self.knots[0] += direction.vector();
+        for i in 1..self.knots.len() {
+            self.knots[i] = follow(self.knots[i - 1], self.knots[i]);
+        }
+        if let Some(tail) = self.knots.last() {
+            self.tails.insert(*tail);
+            trace!(Debug, "step", head = self.knots[0], tail = tail);
+        }
+        trace!(
+            Trace,
+            "knots",
+            positions = self
+                .knots
+                .iter()
+                .map(Point::to_string)
+                .collect::<Vec<_>>()
+                .join(" "),
+        );
+    }
+}
+
+pub struct Visualizer {
+    rope: Rope,
+    moves: Vec<Direction>,
+    done: usize,
+}
+
+pub fn part1_visualizer(input: String) -> Visualizer {
+    Visualizer::new(input.as_str(), 2)
+}
+
+pub fn part2_visualizer(input: String) -> Visualizer {
+    Visualizer::new(input.as_str(), 10)
+}
+
+impl Visualizer {
+    fn new(input: &str, knots: usize) -> Visualizer {
+        let steps = super::shared::must_parse(parse, input);
+        Visualizer {
+            rope: Rope::new(knots),
+            moves: steps
+                .iter()
+                .flat_map(|step| (0..step.distance).map(|_| step.direction))
+                .collect(),
+            done: 0,
+        }
+    }
+}
+
+impl Visualize for Visualizer {
+    fn step(&mut self) -> bool {
+        match self.moves.get(self.done) {
+            Some(direction) => {
+                self.rope.pull(*direction);
+                self.done += 1;
+                true
+            }
+            None => false,
+        }
+    }
+
+    // Draws knots like the puzzle does: H for the head, then T for the tail of
+    // a two-knot rope or numbers for longer ropes. Positions the tail has
+    // visited are marked with #, and the start with s.
+    fn render(&self) -> String {
+        let knots = &self.rope.knots;
+        let tails: SparseGrid<()> = self.rope.tails.iter().map(|tail| (*tail, ())).collect();
+        let grid = tails.render(knots, |position, tail| {
+            match knots.iter().position(|knot| *knot == position) {
+                Some(0) => 'H',
+                Some(_) if knots.len() == 2 => 'T',
+                Some(i) => char::from_digit(i as u32, 36).unwrap_or('?'),
+                None if position == Point::ORIGIN => 's',
+                None if tail.is_some() => '#',
+                None => '.',
+            }
+        });
+        format!(
+            "Move {} of {}, tail visited {} positions\n\n{}",
+            self.done,
+            self.moves.len(),
+            self.rope.tails.len(),
+            grid
+        )
+    }
+}
+
+fn follow(head: Position, tail: Position) -> Position {
+    // We only move when the head is at least 2 away in at least one axis. The
+    // tail then takes one step towards the head along every axis where they
+    // differ, which covers both the straight and diagonal cases.
+    match head.chebyshev(tail) {
+        0 | 1 => tail,
+        2 => tail + (head - tail).signum(),
+        _ => fail!("Impossible: head has distance from tail more than 2: {head:?} {tail:?}"),
+    }
+}
+
+type Position = Point;
+
+#[derive(Debug)]
+pub(super) struct Step {
+    direction: Direction,
+    distance: u32,
+}
+
+pub fn summarize(input: String) -> Summary {
+    let steps = super::shared::must_parse(parse, input.as_str());
+    let distance_towards = |direction: Direction| -> u32 {
+        steps
+            .iter()
+            .filter(|step| step.direction == direction)
+            .map(|step| step.distance)
+            .sum()
+    };
+    Summary::new()
+        .field("steps", steps.len())
+        .field("distance up", distance_towards(Up))
+        .field("distance down", distance_towards(Down))
+        .field("distance left", distance_towards(Left))
+        .field("distance right", distance_towards(Right))
+}
+
+pub fn dump(input: String) -> String {
+    format!("{:#?}", super::shared::must_parse(parse, input.as_str()))
+}
+
+pub(super) fn parse(input: &str) -> IResult<'_, Vec<Step>> {
+    lines(map(
+        separated_pair(
+            map_opt(one_of("UDLR"), Direction::from_letter),
+            char(' '),
+            unsigned,
+        ),
+        |(direction, distance)| Step {
+            direction,
+            distance,
+        },
+    ))(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE_INPUT: &str = "R 4
+U 4
+L 3
+D 1
+R 4
+D 1
+L 5
+R 2
+";
+    const EXAMPLE_INPUT_2: &str = "R 5
+U 8
+L 8
+D 3
+R 17
+D 10
+L 25
+U 20
+";
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(part1(EXAMPLE_INPUT.into()), 13)
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(part2(EXAMPLE_INPUT.into()), 1);
+        assert_eq!(part2(EXAMPLE_INPUT_2.into()), 36);
+    }
+
+    #[test]
+    fn test_visualizer() {
+        let mut visualizer = part1_visualizer(EXAMPLE_INPUT.into());
+        visualizer.step();
+        assert_eq!(
+            visualizer.render(),
+            "Move 1 of 24, tail visited 1 positions\n\nTH\n"
+        );
+        while visualizer.step() {}
+        assert!(visualizer
+            .render()
+            .starts_with("Move 24 of 24, tail visited 13 positions"));
+    }
+}
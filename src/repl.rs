@@ -0,0 +1,289 @@
+use std::{
+    fs,
+    io::{self, BufRead, Write},
+    process::exit,
+};
+
+use clap::Args;
+
+use crate::{
+    config::Config,
+    inputs,
+    puzzles::{
+        self,
+        explore::{self, Explore},
+        params::{self, Params},
+    },
+};
+
+// Explore a puzzle input interactively. Besides the day's own commands (see
+// `puzzles::explore`), every day supports running its parts, changing solver
+// parameters and reloading the input file.
+#[derive(Args, Debug)]
+pub struct ReplArgs {
+    /// Year of puzzle to explore
+    #[arg(short, long, default_value_t = puzzles::DEFAULT_YEAR)]
+    year: u32,
+
+    /// Day number of puzzle to explore
+    #[arg(short, long)]
+    day: u32,
+
+    /// Path to puzzle input file [default: inputs/<YEAR>/day<DAY>.txt]
+    #[arg(short, long)]
+    input_filepath: Option<String>,
+
+    /// Set a solver parameter, as key=value (may be repeated)
+    #[arg(long = "param", value_parser = params::parse_assignment)]
+    params: Vec<(String, String)>,
+}
+
+const GENERIC_COMMANDS: &[(&str, &str)] = &[
+    ("part1", "Solve part 1 with the current parameters"),
+    ("part2", "Solve part 2 with the current parameters"),
+    ("params", "List the current parameters"),
+    ("set <key> <value>", "Set a solver parameter"),
+    ("unset <key>", "Remove a solver parameter"),
+    (
+        "reload [path]",
+        "Reload the input file, or load a different one",
+    ),
+    ("help", "List commands"),
+    ("quit", "Exit the REPL"),
+];
+
+pub fn repl(args: ReplArgs, config: &Config) {
+    if puzzles::find_year(args.year).is_none() {
+        println!("Unknown puzzle year: {}", args.year);
+        exit(1)
+    }
+    if puzzles::find_day(args.year, args.day).is_none() {
+        println!("Unknown puzzle day: {}", args.day);
+        exit(1)
+    }
+    let input_filepath = inputs::find_input(
+        args.input_filepath.as_deref(),
+        &config.inputs_dir(),
+        args.year,
+        args.day,
+    )
+    .unwrap_or_else(|err| {
+        println!("{}", err);
+        exit(1)
+    });
+    let mut session = Session {
+        year: args.year,
+        day: args.day,
+        input_filepath: input_filepath.display().to_string(),
+        input: String::new(),
+        params: config
+            .params(args.year, args.day)
+            .into_iter()
+            .chain(args.params)
+            .collect(),
+        explorer: None,
+    };
+    if let Err(err) = session.reload(None) {
+        println!("{}", err);
+        exit(1)
+    }
+
+    let stdin = io::stdin();
+    if let Err(err) = run(&mut session, stdin.lock(), io::stdout()) {
+        println!("REPL I/O failed: {}", err);
+        exit(1)
+    }
+}
+
+fn run<R: BufRead, W: Write>(
+    session: &mut Session,
+    mut reader: R,
+    mut writer: W,
+) -> io::Result<()> {
+    writeln!(
+        writer,
+        "Loaded day {} input. Type help for commands.",
+        session.day
+    )?;
+    loop {
+        write!(writer, "day{}> ", session.day)?;
+        writer.flush()?;
+
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 {
+            writeln!(writer)?;
+            return Ok(());
+        }
+        match session.eval(line.trim()) {
+            Eval::Output(output) => {
+                if !output.is_empty() {
+                    writeln!(writer, "{}", output)?
+                }
+            }
+            Eval::Error(err) => writeln!(writer, "error: {}", err)?,
+            Eval::Quit => return Ok(()),
+        }
+    }
+}
+
+struct Session {
+    year: u32,
+    day: u32,
+    input_filepath: String,
+    input: String,
+    params: Params,
+    explorer: Option<Box<dyn Explore>>,
+}
+
+#[derive(Debug, PartialEq)]
+enum Eval {
+    Output(String),
+    Error(String),
+    Quit,
+}
+
+impl Session {
+    fn eval(&mut self, line: &str) -> Eval {
+        let mut words = line.split_whitespace();
+        let command = match words.next() {
+            Some(command) => command,
+            None => return Eval::Output(String::new()),
+        };
+        let args: Vec<&str> = words.collect();
+
+        let result = match (command, args.as_slice()) {
+            ("quit" | "exit", _) => return Eval::Quit,
+            ("help", _) => Ok(self.help()),
+            ("part1", []) => self.solve(1),
+            ("part2", []) => self.solve(2),
+            ("params", []) => Ok(self
+                .params
+                .iter()
+                .map(|(key, value)| format!("{}={}", key, value))
+                .collect::<Vec<_>>()
+                .join("\n")),
+            ("set", [key, value]) => {
+                self.params.insert(key, value);
+                Ok(String::new())
+            }
+            ("unset", [key]) => {
+                if self.params.remove(key) {
+                    Ok(String::new())
+                } else {
+                    Err(format!("Parameter not set: {}", key))
+                }
+            }
+            ("reload", []) => self.reload(None),
+            ("reload", [path]) => self.reload(Some(path)),
+            _ => match self.explorer.as_ref().and_then(|explorer| {
+                puzzles::catch_failure(|| explorer.run(command, &args)).transpose()
+            }) {
+                Some(Ok(result)) => result,
+                Some(Err(err)) => Err(err),
+                None => Err(format!("Unknown command or arguments: {}", line)),
+            },
+        };
+
+        match result {
+            Ok(output) => Eval::Output(output),
+            Err(err) => Eval::Error(err),
+        }
+    }
+
+    fn help(&self) -> String {
+        let day_commands = self
+            .explorer
+            .as_ref()
+            .map(|explorer| explorer.commands())
+            .unwrap_or_default();
+        let commands = day_commands.iter().chain(GENERIC_COMMANDS);
+        let width = commands
+            .clone()
+            .map(|(usage, _)| usage.len())
+            .max()
+            .unwrap_or(0);
+        commands
+            .map(|(usage, description)| format!("{:width$}  {}", usage, description))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn solve(&self, part: u32) -> Result<String, String> {
+        puzzles::solve(self.year, self.day, part, self.input.clone(), &self.params)
+            .map_err(|err| err.to_string())
+    }
+
+    // Reads the input file and rebuilds the day's explorer. On failure, the
+    // previously loaded input is kept.
+    fn reload(&mut self, path: Option<&str>) -> Result<String, String> {
+        let path = path.unwrap_or(&self.input_filepath).to_string();
+        let input = fs::read_to_string(&path)
+            .map_err(|err| format!("Could not open puzzle input file: {}", err))?;
+        self.load(input)?;
+        self.input_filepath = path;
+        Ok(format!("Loaded {}", self.input_filepath))
+    }
+
+    fn load(&mut self, input: String) -> Result<(), String> {
+        self.explorer = match explore::explorer(self.year, self.day) {
+            Some(explorer) => {
+                let input = input.clone();
+                Some(puzzles::catch_failure(|| explorer(input))?)
+            }
+            None => None,
+        };
+        self.input = input;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn session(day: u32, input: &str) -> Session {
+        let mut session = Session {
+            year: puzzles::DEFAULT_YEAR,
+            day,
+            input_filepath: String::new(),
+            input: String::new(),
+            params: Params::new(),
+            explorer: None,
+        };
+        session.load(input.into()).unwrap();
+        session
+    }
+
+    #[test]
+    fn test_eval() {
+        let mut session = session(13, "[1,1,3,1,1]\n[1,1,5,1,1]\n");
+        assert_eq!(session.eval("part1"), Eval::Output("1".into()));
+        assert_eq!(
+            session.eval("compare [1] [[0]]"),
+            Eval::Output("out of order".into())
+        );
+        assert!(matches!(session.eval("compare [1]"), Eval::Error(_)));
+        assert!(matches!(session.eval("frobnicate"), Eval::Error(_)));
+        assert_eq!(session.eval("quit"), Eval::Quit);
+    }
+
+    #[test]
+    fn test_params() {
+        let mut session = session(15, "Sensor at x=8, y=7: closest beacon is at x=2, y=10\n");
+        assert_eq!(session.eval("set row 10"), Eval::Output(String::new()));
+        assert_eq!(session.eval("params"), Eval::Output("row=10".into()));
+        assert_eq!(session.eval("part1"), Eval::Output("12".into()));
+        assert!(matches!(session.eval("unset search_area"), Eval::Error(_)));
+    }
+
+    #[test]
+    fn test_run() {
+        let mut session = session(6, "mjqjpqmgbljsphdztnvjfqwrcgsmlb");
+        let mut output = Vec::new();
+        run(&mut session, "part1\nquit\n".as_bytes(), &mut output).unwrap();
+        assert_eq!(
+            String::from_utf8(output).unwrap(),
+            "Loaded day 6 input. Type help for commands.\nday6> 7\nday6> "
+        );
+    }
+}
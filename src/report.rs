@@ -0,0 +1,82 @@
+use std::{fs, process::exit};
+
+use clap::Args;
+
+use crate::{
+    config::Config,
+    inputs,
+    puzzles::{
+        self,
+        params::{self, Params},
+        report::reporter,
+    },
+};
+
+#[derive(Args, Debug)]
+pub struct ReportArgs {
+    /// Year of puzzle input to report on
+    #[arg(short, long, default_value_t = puzzles::DEFAULT_YEAR)]
+    year: u32,
+
+    /// Day number of puzzle input to report on
+    #[arg(short, long)]
+    day: u32,
+
+    /// Path to puzzle input file [default: inputs/<YEAR>/day<DAY>.txt]
+    #[arg(short, long)]
+    input_filepath: Option<String>,
+
+    /// Override a report parameter, as key=value (may be repeated)
+    #[arg(long = "param", value_parser = params::parse_assignment)]
+    params: Vec<(String, String)>,
+}
+
+pub fn report(args: ReportArgs, config: &Config) {
+    let reporter = match (
+        puzzles::find_year(args.year),
+        puzzles::find_day(args.year, args.day),
+        reporter(args.year, args.day),
+    ) {
+        (None, _, _) => {
+            println!("Unknown puzzle year: {}", args.year);
+            exit(1)
+        }
+        (Some(_), None, _) => {
+            println!("Unknown puzzle day: {}", args.day);
+            exit(1)
+        }
+        (Some(_), Some(_), None) => {
+            println!("Reports on day {} inputs are not supported", args.day);
+            exit(1)
+        }
+        (Some(_), Some(_), Some(reporter)) => reporter,
+    };
+
+    let input_filepath = inputs::find_input(
+        args.input_filepath.as_deref(),
+        &config.inputs_dir(),
+        args.year,
+        args.day,
+    )
+    .unwrap_or_else(|err| {
+        println!("{}", err);
+        exit(1)
+    });
+    let input = fs::read_to_string(input_filepath).unwrap_or_else(|err| {
+        println!("Could not open puzzle input file: {}", err);
+        exit(1)
+    });
+    let params: Params = config
+        .params(args.year, args.day)
+        .into_iter()
+        .chain(args.params)
+        .collect();
+
+    match puzzles::catch_failure(|| reporter(input, &params)) {
+        Ok(report) => print!("{}", report),
+        Err(err) => {
+            println!("{}", err);
+            exit(1)
+        }
+    }
+}
@@ -0,0 +1,426 @@
+use std::{
+    io::{self, BufRead, BufReader, Read, Write},
+    net::{TcpListener, TcpStream},
+    process::exit,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+use clap::Args;
+
+use crate::{
+    output::json_string,
+    puzzles::{self, params::Params, SolveError},
+};
+
+// Serve solvers as a small HTTP/1.1 API on localhost:
+//
+// - `GET /health` responds with `{"status":"ok"}`.
+// - `POST /solve?day=15&part=1&row=10` runs the solver with the request body
+//   as the puzzle input. The puzzle's `year` may also be given, and defaults
+//   to 2022. Other query parameters are passed to the solver as parameters.
+//   Responds with the answer and timings.
+//
+// Every connection handles exactly one request. Connections beyond the
+// concurrency limit are turned away with a 503 instead of being queued.
+#[derive(Args, Debug, Clone)]
+pub struct ServeArgs {
+    /// Port to listen on
+    #[arg(long, default_value_t = 2022)]
+    port: u16,
+
+    /// Address to bind to
+    #[arg(long, default_value = "127.0.0.1")]
+    address: String,
+
+    /// Largest accepted request body (the puzzle input), in bytes
+    #[arg(long, default_value_t = 1 << 20)]
+    max_body_bytes: usize,
+
+    /// Largest number of requests handled at the same time
+    #[arg(long, default_value_t = 4)]
+    max_connections: usize,
+
+    /// Seconds to wait on a slow client before giving up on its request
+    #[arg(long, default_value_t = 10)]
+    read_timeout_secs: u64,
+}
+
+// Largest accepted request line or header line. Longer lines are rejected
+// rather than buffered.
+const MAX_LINE_BYTES: usize = 8 * 1024;
+const MAX_HEADERS: usize = 64;
+
+pub fn serve(args: ServeArgs) {
+    let listener = TcpListener::bind((args.address.as_str(), args.port)).unwrap_or_else(|err| {
+        println!(
+            "Could not listen on {}:{}: {}",
+            args.address, args.port, err
+        );
+        exit(1)
+    });
+    match listener.local_addr() {
+        Ok(addr) => eprintln!("Listening on http://{}", addr),
+        Err(err) => eprintln!("Listening on unknown address: {}", err),
+    }
+
+    let active = Arc::new(AtomicUsize::new(0));
+    for stream in listener.incoming() {
+        let mut stream = match stream {
+            Ok(stream) => stream,
+            Err(err) => {
+                eprintln!("Could not accept connection: {}", err);
+                continue;
+            }
+        };
+
+        let slot = match ConnectionSlot::acquire(&active, args.max_connections) {
+            Some(slot) => slot,
+            None => {
+                let response = Response::error(503, "Too many concurrent requests");
+                let _ = response.write_to(&mut stream);
+                continue;
+            }
+        };
+        let args = args.clone();
+        thread::spawn(move || {
+            let _slot = slot;
+            if let Err(err) = handle_connection(stream, &args) {
+                eprintln!("Could not handle connection: {}", err);
+            }
+        });
+    }
+}
+
+// Counts a connection against the concurrency limit for as long as it's held.
+struct ConnectionSlot(Arc<AtomicUsize>);
+
+impl ConnectionSlot {
+    fn acquire(active: &Arc<AtomicUsize>, limit: usize) -> Option<ConnectionSlot> {
+        if active.fetch_add(1, Ordering::SeqCst) < limit {
+            Some(ConnectionSlot(active.clone()))
+        } else {
+            active.fetch_sub(1, Ordering::SeqCst);
+            None
+        }
+    }
+}
+
+impl Drop for ConnectionSlot {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+fn handle_connection(mut stream: TcpStream, args: &ServeArgs) -> io::Result<()> {
+    let timeout = Some(Duration::from_secs(args.read_timeout_secs));
+    stream.set_read_timeout(timeout)?;
+    stream.set_write_timeout(timeout)?;
+
+    let response = match read_request(&mut BufReader::new(&stream), args.max_body_bytes) {
+        Ok(request) => route(&request),
+        Err(response) => response,
+    };
+    response.write_to(&mut stream)
+}
+
+#[derive(Debug, PartialEq)]
+struct Request {
+    method: String,
+    path: String,
+    query: Vec<(String, String)>,
+    body: Vec<u8>,
+}
+
+fn read_request<R: BufRead>(reader: &mut R, max_body_bytes: usize) -> Result<Request, Response> {
+    let request_line = read_line(reader)?;
+    let (method, target) = match request_line.split(' ').collect::<Vec<_>>()[..] {
+        [method, target, version] if version.starts_with("HTTP/1.") => (method, target),
+        _ => return Err(Response::error(400, "Malformed request line")),
+    };
+    let (path, query) = match target.split_once('?') {
+        Some((path, query)) => (path, parse_query(query)?),
+        None => (target, Vec::new()),
+    };
+
+    let mut content_length = None;
+    for i in 0.. {
+        let line = read_line(reader)?;
+        if line.is_empty() {
+            break;
+        }
+        if i == MAX_HEADERS {
+            return Err(Response::error(431, "Too many request headers"));
+        }
+        let (name, value) = line
+            .split_once(':')
+            .ok_or_else(|| Response::error(400, "Malformed request header"))?;
+        if name.eq_ignore_ascii_case("content-length") {
+            let length = value
+                .trim()
+                .parse::<usize>()
+                .map_err(|_| Response::error(400, "Invalid Content-Length"))?;
+            content_length = Some(length);
+        } else if name.eq_ignore_ascii_case("transfer-encoding") {
+            return Err(Response::error(501, "Transfer-Encoding is not supported"));
+        }
+    }
+
+    let body = match (method, content_length) {
+        (_, Some(length)) if length > max_body_bytes => {
+            return Err(Response::error(
+                413,
+                &format!("Request body exceeds limit of {} bytes", max_body_bytes),
+            ))
+        }
+        (_, Some(length)) => {
+            let mut body = vec![0; length];
+            reader
+                .read_exact(&mut body)
+                .map_err(|_| Response::error(400, "Request body shorter than Content-Length"))?;
+            body
+        }
+        ("POST", None) => return Err(Response::error(411, "Content-Length is required")),
+        (_, None) => Vec::new(),
+    };
+
+    Ok(Request {
+        method: method.to_string(),
+        path: path.to_string(),
+        query,
+        body,
+    })
+}
+
+// Reads a CRLF (or LF) terminated line, refusing to buffer overlong lines.
+fn read_line<R: BufRead>(reader: &mut R) -> Result<String, Response> {
+    let mut line = Vec::new();
+    reader
+        .take(MAX_LINE_BYTES as u64 + 1)
+        .read_until(b'\n', &mut line)
+        .map_err(|_| Response::error(400, "Could not read request"))?;
+    if line.len() > MAX_LINE_BYTES {
+        return Err(Response::error(431, "Request line or header too long"));
+    }
+    if line.pop() != Some(b'\n') {
+        return Err(Response::error(400, "Incomplete request"));
+    }
+    if line.last() == Some(&b'\r') {
+        line.pop();
+    }
+    String::from_utf8(line).map_err(|_| Response::error(400, "Request is not valid UTF-8"))
+}
+
+fn parse_query(query: &str) -> Result<Vec<(String, String)>, Response> {
+    query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+            Ok((percent_decode(key)?, percent_decode(value)?))
+        })
+        .collect()
+}
+
+fn percent_decode(s: &str) -> Result<String, Response> {
+    let invalid = || Response::error(400, "Invalid percent-encoding in query");
+    let mut bytes = Vec::new();
+    let mut iter = s.bytes();
+    while let Some(b) = iter.next() {
+        match b {
+            b'%' => {
+                let hex = [
+                    iter.next().ok_or_else(invalid)?,
+                    iter.next().ok_or_else(invalid)?,
+                ];
+                let hex = std::str::from_utf8(&hex).map_err(|_| invalid())?;
+                bytes.push(u8::from_str_radix(hex, 16).map_err(|_| invalid())?);
+            }
+            b'+' => bytes.push(b' '),
+            b => bytes.push(b),
+        }
+    }
+    String::from_utf8(bytes).map_err(|_| invalid())
+}
+
+fn route(request: &Request) -> Response {
+    match (request.method.as_str(), request.path.as_str()) {
+        ("GET", "/health") => Response::json(200, "{\"status\":\"ok\"}".into()),
+        ("POST", "/solve") => solve(request),
+        (_, "/health") | (_, "/solve") => Response::error(405, "Method not allowed"),
+        _ => Response::error(404, "Not found"),
+    }
+}
+
+fn solve(request: &Request) -> Response {
+    let mut year = Some(puzzles::DEFAULT_YEAR);
+    let mut day = None;
+    let mut part = None;
+    let mut params = Params::new();
+    for (key, value) in &request.query {
+        match key.as_str() {
+            "year" => year = value.parse::<u32>().ok(),
+            "day" => day = value.parse::<u32>().ok(),
+            "part" => part = value.parse::<u32>().ok(),
+            _ => params.insert(key, value),
+        }
+    }
+    let (year, day, part) = match (year, day, part) {
+        (Some(year), Some(day), Some(part)) => (year, day, part),
+        (None, _, _) => return Response::error(400, "Query parameter year must be a number"),
+        _ => return Response::error(400, "Query parameters day and part are required"),
+    };
+    let input = match String::from_utf8(request.body.clone()) {
+        Ok(input) => input,
+        Err(_) => return Response::error(400, "Puzzle input is not valid UTF-8"),
+    };
+
+    let start = Instant::now();
+    let result = puzzles::solve(year, day, part, input, &params);
+    let elapsed = start.elapsed();
+
+    match result {
+        Ok(answer) => Response::json(
+            200,
+            format!(
+                "{{\"day\":{},\"part\":{},\"answer\":{},\"timings\":{{\"solve_us\":{}}}}}",
+                day,
+                part,
+                json_string(&answer),
+                elapsed.as_micros()
+            ),
+        ),
+        Err(
+            err @ (SolveError::UnknownYear(_)
+            | SolveError::UnknownDay(_)
+            | SolveError::UnknownPart(_, _)),
+        ) => Response::error(404, &err.to_string()),
+        Err(err @ SolveError::Failed(_)) => Response::error(422, &err.to_string()),
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct Response {
+    status: u16,
+    body: String,
+}
+
+impl Response {
+    fn json(status: u16, body: String) -> Response {
+        Response { status, body }
+    }
+
+    fn error(status: u16, message: &str) -> Response {
+        Response::json(status, format!("{{\"error\":{}}}", json_string(message)))
+    }
+
+    fn write_to<W: Write>(&self, writer: &mut W) -> io::Result<()> {
+        write!(
+            writer,
+            "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            self.status,
+            reason_phrase(self.status),
+            self.body.len(),
+            self.body
+        )?;
+        writer.flush()
+    }
+}
+
+fn reason_phrase(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        400 => "Bad Request",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        411 => "Length Required",
+        413 => "Payload Too Large",
+        422 => "Unprocessable Entity",
+        431 => "Request Header Fields Too Large",
+        501 => "Not Implemented",
+        503 => "Service Unavailable",
+        _ => "Unknown",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(raw: &str) -> Result<Request, Response> {
+        read_request(&mut raw.as_bytes(), 64)
+    }
+
+    #[test]
+    fn test_health() {
+        let request = request("GET /health HTTP/1.1\r\nHost: localhost\r\n\r\n").unwrap();
+        assert_eq!(
+            route(&request),
+            Response::json(200, "{\"status\":\"ok\"}".into())
+        );
+    }
+
+    #[test]
+    fn test_solve() {
+        let request = request(
+            "POST /solve?day=2&part=1 HTTP/1.1\r\nContent-Length: 12\r\n\r\nA Y\nB X\nC Z\n",
+        )
+        .unwrap();
+        let response = route(&request);
+        assert_eq!(response.status, 200);
+        assert!(response
+            .body
+            .starts_with("{\"day\":2,\"part\":1,\"answer\":\"15\",\"timings\":{\"solve_us\":"));
+    }
+
+    #[test]
+    fn test_solve_year() {
+        let solve = |query: &str| {
+            route(
+                &request(&format!(
+                    "POST /solve?{} HTTP/1.1\r\nContent-Length: 4\r\n\r\nA Y\n",
+                    query
+                ))
+                .unwrap(),
+            )
+        };
+        assert_eq!(solve("year=2022&day=2&part=1").status, 200);
+        assert_eq!(solve("year=2015&day=2&part=1").status, 404);
+        assert_eq!(solve("year=next&day=2&part=1").status, 400);
+    }
+
+    #[test]
+    fn test_solve_params() {
+        let request =
+            request("POST /solve?day=15&part=1&row=10 HTTP/1.1\r\nContent-Length: 0\r\n\r\n")
+                .unwrap();
+        assert_eq!(request.query[2], ("row".to_string(), "10".to_string()));
+    }
+
+    #[test]
+    fn test_solve_invalid_input() {
+        let request =
+            request("POST /solve?day=2&part=1 HTTP/1.1\r\nContent-Length: 4\r\n\r\nQ Y\n").unwrap();
+        assert_eq!(route(&request).status, 422);
+    }
+
+    #[test]
+    fn test_limits() {
+        assert_eq!(
+            request("POST /solve?day=1&part=1 HTTP/1.1\r\nContent-Length: 65\r\n\r\n")
+                .unwrap_err()
+                .status,
+            413
+        );
+        assert_eq!(
+            request("POST /solve?day=1&part=1 HTTP/1.1\r\n\r\n")
+                .unwrap_err()
+                .status,
+            411
+        );
+    }
+}
@@ -0,0 +1,174 @@
+use std::{
+    io::{self, BufRead, Write},
+    thread,
+    time::Duration,
+};
+
+use clap::Args;
+
+use crate::puzzles::visualize::Visualize;
+
+#[derive(Args, Debug)]
+pub struct VisualizeArgs {
+    /// Animate the puzzle's simulation in the terminal before printing the answer
+    #[arg(long)]
+    pub visualize: bool,
+
+    /// Milliseconds to wait between frames
+    #[arg(long, default_value_t = 50, value_name = "MS", requires = "visualize")]
+    pub frame_delay: u64,
+
+    /// Wait for Enter before drawing each frame (enter q to stop watching)
+    #[arg(long, requires = "visualize")]
+    pub step: bool,
+
+    /// Number of frames to skip between drawn frames, for long simulations
+    #[arg(long, default_value_t = 0, value_name = "N", requires = "visualize")]
+    pub skip_frames: usize,
+}
+
+// Moves the cursor to the top left and clears the screen, so that each frame
+// is drawn over the last one.
+const REDRAW: &str = "\x1b[H\x1b[2J";
+const HIDE_CURSOR: &str = "\x1b[?25l";
+const SHOW_CURSOR: &str = "\x1b[?25h";
+
+pub fn play(visualization: &mut dyn Visualize, args: &VisualizeArgs) {
+    let stdin = io::stdin();
+    let stdout = io::stdout();
+    if let Err(err) = play_to(visualization, args, &mut stdin.lock(), &mut stdout.lock()) {
+        eprintln!("Could not draw visualization: {}", err)
+    }
+}
+
+// Draws the first frame, every frame after each skipped run of frames, and the
+// last frame. Returns the number of frames drawn.
+fn play_to<R: BufRead, W: Write>(
+    visualization: &mut dyn Visualize,
+    args: &VisualizeArgs,
+    input: &mut R,
+    output: &mut W,
+) -> io::Result<usize> {
+    write!(output, "{}", HIDE_CURSOR)?;
+    let result = (|| {
+        let mut frame = 0;
+        let mut drawn = 0;
+        loop {
+            if !draw(visualization, args, frame, input, output)? {
+                return Ok(drawn + 1);
+            }
+            drawn += 1;
+
+            // Skip ahead, stopping early to draw the last frame.
+            let mut finished = true;
+            for _ in 0..=args.skip_frames {
+                if !visualization.step() {
+                    break;
+                }
+                frame += 1;
+                finished = false;
+            }
+            if finished {
+                return Ok(drawn);
+            }
+        }
+    })();
+    write!(output, "{}", SHOW_CURSOR)?;
+    output.flush()?;
+    result
+}
+
+// Draws a frame, then waits before the next one. Returns false if the viewer
+// asked to stop.
+fn draw<R: BufRead, W: Write>(
+    visualization: &dyn Visualize,
+    args: &VisualizeArgs,
+    frame: usize,
+    input: &mut R,
+    output: &mut W,
+) -> io::Result<bool> {
+    write!(
+        output,
+        "{}{}\nFrame {}\n",
+        REDRAW,
+        visualization.render(),
+        frame
+    )?;
+    if args.step {
+        write!(output, "Press Enter for the next frame, or q to stop: ")?;
+        output.flush()?;
+        let mut line = String::new();
+        // Stop at the end of input too, rather than spinning through frames.
+        Ok(input.read_line(&mut line)? > 0 && line.trim() != "q")
+    } else {
+        output.flush()?;
+        thread::sleep(Duration::from_millis(args.frame_delay));
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Counts to a number, one step at a time.
+    struct Counter {
+        count: u32,
+        until: u32,
+    }
+
+    impl Visualize for Counter {
+        fn step(&mut self) -> bool {
+            if self.count < self.until {
+                self.count += 1;
+                true
+            } else {
+                false
+            }
+        }
+
+        fn render(&self) -> String {
+            format!("count={}", self.count)
+        }
+    }
+
+    fn args(step: bool, skip_frames: usize) -> VisualizeArgs {
+        VisualizeArgs {
+            visualize: true,
+            frame_delay: 0,
+            step,
+            skip_frames,
+        }
+    }
+
+    fn play(until: u32, args: &VisualizeArgs, input: &str) -> (usize, String) {
+        let mut counter = Counter { count: 0, until };
+        let mut output = Vec::new();
+        let drawn = play_to(&mut counter, args, &mut input.as_bytes(), &mut output).unwrap();
+        (drawn, String::from_utf8(output).unwrap())
+    }
+
+    #[test]
+    fn test_play() {
+        let (drawn, output) = play(3, &args(false, 0), "");
+        assert_eq!(drawn, 4);
+        assert!(output.contains("count=3\nFrame 3\n"));
+        assert!(output.ends_with(SHOW_CURSOR));
+    }
+
+    #[test]
+    fn test_skip_frames() {
+        // Frames 0, 3, 6, 9 and the last frame, 10.
+        let (drawn, output) = play(10, &args(false, 2), "");
+        assert_eq!(drawn, 5);
+        assert!(output.contains("count=9\n") && output.contains("count=10\n"));
+        assert!(!output.contains("count=8\n"));
+    }
+
+    #[test]
+    fn test_step_mode() {
+        let (drawn, output) = play(10, &args(true, 0), "\n\nq\n");
+        assert_eq!(drawn, 3);
+        assert!(output.contains("count=2\n") && !output.contains("count=3\n"));
+    }
+}